@@ -32,7 +32,7 @@ def_machine! {
 
 fn main () {
   use std::io::Write;
-  use macro_machines::MachineDotfile;
+  use macro_machines::{Defaults, DotfileOptions, MachineDotfile};
 
   let example_name = std::env::current_exe().unwrap().file_name().unwrap()
     .to_str().unwrap().to_string();
@@ -47,12 +47,15 @@ fn main () {
 
   let dotfile_name = format!("{example_name}.dot");
   let mut f = std::fs::File::create (dotfile_name).unwrap();
-  f.write_all (Door::dotfile_hide_actions().as_bytes()).unwrap();
+  f.write_all (Door::dotfile_with (&DotfileOptions::new().actions (false))
+    .as_bytes()).unwrap();
   drop (f);
 
   let dotfile_name = format!("{example_name}-show-defaults.dot");
   let mut f = std::fs::File::create (dotfile_name).unwrap();
-  f.write_all (Door::dotfile_show_defaults().as_bytes()).unwrap();
+  f.write_all (Door::dotfile_with (
+    &DotfileOptions::new().defaults (Defaults::Expression)
+  ).as_bytes()).unwrap();
   drop (f);
 
   let mut door = Door::initial();
@@ -16,6 +16,9 @@ def_machine_debug! {
       event Open  <Closed> => <Opened> ()  {} => { *open_count += 1; }
       event Close <Opened> => <Closed> ()
     ]
+    logging: {
+      rejected: Info
+    }
     initial_state:  Closed {
       initial_action: { println!("hello"); }
     }
@@ -30,7 +33,7 @@ def_machine_debug! {
 
 fn main () {
   use std::io::Write;
-  use macro_machines::MachineDotfile;
+  use macro_machines::{Defaults, DotfileOptions, MachineDotfile};
 
   let example_name = std::env::current_exe().unwrap().file_name().unwrap()
     .to_str().unwrap().to_string();
@@ -50,10 +53,13 @@ fn main () {
 
   let dotfile_name = format!("{example_name}-show-defaults.dot");
   let mut f = std::fs::File::create (dotfile_name).unwrap();
-  f.write_all (Door::dotfile_show_defaults().as_bytes()).unwrap();
+  f.write_all (Door::dotfile_with (
+    &DotfileOptions::new().defaults (Defaults::Expression)
+  ).as_bytes()).unwrap();
   drop (f);
 
   let mut door = Door::initial();
+  door.set_instance_name ("front".to_string());
   println!("door: {door:?}");
 
   door.handle_event (EventId::Knock.into()).unwrap();
@@ -24,7 +24,8 @@ struct Nodebug {
 
 fn main () {
   use std::io::Write;
-  use macro_machines::{HandleEventException, MachineDotfile};
+  use macro_machines::{
+    Defaults, DotfileOptions, HandleEventException, MachineDotfile};
 
   let example_name = std::env::current_exe().unwrap().file_name().unwrap()
     .to_str().unwrap().to_string();
@@ -40,7 +41,9 @@ fn main () {
 
   let dotfile_name = format!("{example_name}.dot");
   let mut f = std::fs::File::create (dotfile_name).unwrap();
-  f.write_all (G::<Nodebug>::dotfile_show_defaults().as_bytes()).unwrap();
+  f.write_all (G::<Nodebug>::dotfile_with (
+    &DotfileOptions::new().defaults (Defaults::Expression)
+  ).as_bytes()).unwrap();
   drop (f);
 
   //let mut g = G::<std::sync::mpsc::Receiver <f64>>::initial();
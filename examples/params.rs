@@ -18,7 +18,7 @@ def_machine_debug!{
 
 fn main () {
   use std::io::Write;
-  use macro_machines::MachineDotfile;
+  use macro_machines::{Defaults, DotfileOptions, MachineDotfile};
 
   let example_name = std::env::current_exe().unwrap().file_name().unwrap()
     .to_str().unwrap().to_string();
@@ -33,7 +33,9 @@ fn main () {
 
   let dotfile_name = format!("{example_name}.dot");
   let mut f = std::fs::File::create (dotfile_name).unwrap();
-  f.write_all (M::dotfile_show_defaults().as_bytes()).unwrap();
+  f.write_all (M::dotfile_with (
+    &DotfileOptions::new().defaults (Defaults::Expression)
+  ).as_bytes()).unwrap();
   drop (f);
 
   let mut m = M::initial();
@@ -0,0 +1,139 @@
+//! Structural analysis of state machine definitions.
+//!
+//! ```text
+//! let report = analysis::analyze::<Door>();
+//! assert!(report.is_ok(), "{report}");
+//! // or equivalently:
+//! analysis::assert_structure::<Door>();
+//! ```
+
+use crate::MachineMetadata;
+
+/// Structural problems found in a state machine definition
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Report <S, E> {
+  /// States that can not be reached from the initial state
+  pub unreachable_states     : Vec <S>,
+  /// Non-terminal states that can not be left: there is no external event
+  /// from the state or universal event to a different state
+  pub dead_end_states        : Vec <S>,
+  /// States from which the terminal state can not be reached; always empty
+  /// if no terminal state is defined
+  pub non_terminating_states : Vec <S>,
+  /// Events that can never be handled since their source state can not be
+  /// reached
+  pub dead_events            : Vec <E>
+}
+
+impl <S, E> Report <S, E> {
+  /// Returns true if no problems were found
+  pub fn is_ok (&self) -> bool {
+    self.unreachable_states.is_empty() && self.dead_end_states.is_empty() &&
+    self.non_terminating_states.is_empty() && self.dead_events.is_empty()
+  }
+}
+
+impl <S : std::fmt::Debug, E : std::fmt::Debug> std::fmt::Display
+  for Report <S, E>
+{
+  fn fmt (&self, f : &mut std::fmt::Formatter) -> std::fmt::Result {
+    if self.is_ok() {
+      return write!(f, "no structural problems")
+    }
+    write_items (f, "unreachable states", &self.unreachable_states)?;
+    write_items (f, "dead end states", &self.dead_end_states)?;
+    write_items (f, "states that can not reach the terminal state",
+      &self.non_terminating_states)?;
+    write_items (f, "events that can never be handled", &self.dead_events)
+  }
+}
+
+/// Analyze the structure of the state machine `M`
+///
+/// # Panics
+///
+/// Panics if a state of `TRANSITIONS` or the initial or terminal state is
+/// missing from `STATES`, which can not happen for generated machines.
+pub fn analyze <M : MachineMetadata> () -> Report <M::StateId, M::EventId> {
+  let states = M::STATES;
+  let index  = |state : &M::StateId| states.iter().position (|s| s == state)
+    .expect ("state should be in STATES");
+  // successors[i]: states that are entered by an event handled in state i,
+  // excluding internal events
+  let mut successors = vec![Vec::new(); states.len()];
+  for (_, source, target) in M::TRANSITIONS {
+    let Some (target) = target.as_ref().map (index) else {
+      continue   // internal event
+    };
+    match source.as_ref().map (index) {
+      Some (source) => successors[source].push (target),
+      None => successors.iter_mut().for_each (|s| s.push (target))
+    }
+  }
+  let reachable = reachable_from (&successors, index (&M::STATE_INITIAL));
+  let unreachable_states = states.iter().enumerate()
+    .filter (|(i, _)| !reachable[*i]).map (|(_, s)| s.clone()).collect();
+  let terminal = M::STATE_TERMINAL.as_ref().map (index);
+  let dead_end_states = states.iter().enumerate()
+    .filter (|(i, _)| Some (*i) != terminal &&
+      successors[*i].iter().all (|target| target == i))
+    .map (|(_, s)| s.clone()).collect();
+  let non_terminating_states = if let Some (terminal) = terminal {
+    // reverse edges
+    let mut predecessors = vec![Vec::new(); states.len()];
+    for (source, targets) in successors.iter().enumerate() {
+      for target in targets {
+        predecessors[*target].push (source);
+      }
+    }
+    let terminating = reachable_from (&predecessors, terminal);
+    states.iter().enumerate().filter (|(i, _)| !terminating[*i])
+      .map (|(_, s)| s.clone()).collect()
+  } else {
+    Vec::new()
+  };
+  let dead_events = M::TRANSITIONS.iter()
+    .filter (|(_, source, _)| source.as_ref()
+      .is_some_and (|source| !reachable[index (source)]))
+    .map (|(event, _, _)| event.clone()).collect();
+  Report {
+    unreachable_states, dead_end_states, non_terminating_states, dead_events
+  }
+}
+
+/// Check the structure of the state machine `M`; intended for use in tests
+///
+/// # Panics
+///
+/// Panics with the report if any structural problems are found.
+pub fn assert_structure <M : MachineMetadata> () {
+  let report = analyze::<M>();
+  assert!(report.is_ok(), "state machine structural problems:\n{report}");
+}
+
+/// Write a line listing the items if there are any
+fn write_items <T : std::fmt::Debug> (
+  f : &mut std::fmt::Formatter, description : &str, items : &[T]
+) -> std::fmt::Result {
+  if items.is_empty() {
+    Ok (())
+  } else {
+    writeln!(f, "{description}: {items:?}")
+  }
+}
+
+/// Mark all nodes reachable from the start node
+fn reachable_from (edges : &[Vec <usize>], start : usize) -> Vec <bool> {
+  let mut reachable = vec![false; edges.len()];
+  reachable[start] = true;
+  let mut stack = vec![start];
+  while let Some (node) = stack.pop() {
+    for next in &edges[node] {
+      if !reachable[*next] {
+        reachable[*next] = true;
+        stack.push (*next);
+      }
+    }
+  }
+  reachable
+}
@@ -0,0 +1,28 @@
+//! Print a `def_machine!` skeleton for an SCXML file, or standard input if no
+//! file is given
+
+use std::io::Read;
+
+fn main() {
+  let scxml = match std::env::args().nth (1) {
+    Some (path) => std::fs::read_to_string (&path).unwrap_or_else (|err| {
+      eprintln!("error reading {path}: {err}");
+      std::process::exit (1)
+    }),
+    None => {
+      let mut scxml = String::new();
+      std::io::stdin().read_to_string (&mut scxml).unwrap_or_else (|err| {
+        eprintln!("error reading standard input: {err}");
+        std::process::exit (1)
+      });
+      scxml
+    }
+  };
+  match macro_machines::scxml::to_def_machine (&scxml) {
+    Ok (machine) => print!("{machine}"),
+    Err (err) => {
+      eprintln!("{err}");
+      std::process::exit (1)
+    }
+  }
+}
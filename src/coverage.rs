@@ -0,0 +1,195 @@
+//! Optional runtime transition coverage for state machine instances.
+//!
+//! Coverage tracking is enabled per instance with `enable_coverage`, after
+//! which the machine records which transitions have been taken from which
+//! source state and which state entry and exit actions have run. Universal
+//! transitions are covered separately for each state they may be taken from.
+//! A `Report` of the transitions and actions that were not covered is returned
+//! by the `coverage` method of the machine.
+//! Reports of several instances of the same machine may be combined with
+//! `Report::merge`, and `MachineDotfile::dotfile_coverage` renders a report as
+//! a diagram with covered transitions in green and uncovered transitions in
+//! red:
+//!
+//! ```text
+//! door.enable_coverage();
+//! door.handle_event (EventId::Knock.into()).unwrap();
+//! let report = door.coverage().unwrap();
+//! print!("{report}");
+//! std::fs::write ("door-coverage.dot", Door::dotfile_coverage (&report))?;
+//! ```
+
+/// Records coverage for a single machine instance.
+///
+/// States and events are identified by the index of their ID variant.
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct Tracker {
+  /// Handled events for each source state
+  transitions   : Vec <Vec <bool>>,
+  entry_actions : Vec <bool>,
+  exit_actions  : Vec <bool>
+}
+
+impl Tracker {
+  pub fn new (state_count : usize, event_count : usize) -> Self {
+    Tracker {
+      transitions:   vec![vec![false; event_count]; state_count],
+      entry_actions: vec![false; state_count],
+      exit_actions:  vec![false; state_count]
+    }
+  }
+
+  pub fn event_handled (&mut self, source : usize, event : usize) {
+    self.transitions[source][event] = true;
+  }
+
+  pub fn state_entered (&mut self, state : usize) {
+    self.entry_actions[state] = true;
+  }
+
+  pub fn state_exited (&mut self, state : usize) {
+    self.exit_actions[state] = true;
+  }
+
+  /// Coverage of the given transitions, given as event, source and target
+  /// state names in order of event ID variants, and of the state entry and
+  /// exit actions, given as the state name and whether the state has an entry
+  /// and exit action in order of state ID variants.
+  ///
+  /// Universal transitions are reported once for each state.
+  pub fn report (&self,
+    machine     : &'static str,
+    transitions
+      : &[(&'static str, Option <&'static str>, Option <&'static str>)],
+    states      : &[(&'static str, bool, bool)]
+  ) -> Report {
+    debug_assert_eq!(states.len(), self.transitions.len());
+    debug_assert_eq!(states.len(), self.entry_actions.len());
+    let actions = |covered : &[bool], has_action : fn (&(_, bool, bool)) -> bool|
+      states.iter().zip (covered).filter (|(state, _)| has_action (state))
+        .map (|((state, _, _), covered)| Action {
+          state, covered: *covered
+        }).collect();
+    let mut covered_transitions = Vec::with_capacity (transitions.len());
+    for (i, (event, source, target)) in transitions.iter().enumerate() {
+      for (j, (state, _, _)) in states.iter().enumerate() {
+        if source.is_none_or (|source| source == *state) {
+          covered_transitions.push (Transition {
+            event, source: state, universal: source.is_none(), target: *target,
+            covered: self.transitions[j][i]
+          });
+        }
+      }
+    }
+    Report {
+      machine,
+      transitions:   covered_transitions,
+      entry_actions: actions (&self.entry_actions, |state| state.1),
+      exit_actions:  actions (&self.exit_actions,  |state| state.2)
+    }
+  }
+}
+
+/// Coverage of the transitions and actions of a machine
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Report {
+  pub machine       : &'static str,
+  /// Each transition in order of definition, with universal transitions
+  /// repeated for each source state in order of definition
+  pub transitions   : Vec <Transition>,
+  /// Each state with an entry action in order of definition
+  pub entry_actions : Vec <Action>,
+  /// Each state with an exit action in order of definition
+  pub exit_actions  : Vec <Action>
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Transition {
+  pub event     : &'static str,
+  /// State the transition was taken from
+  pub source    : &'static str,
+  /// Whether the transition is a universal transition taken from `source`
+  pub universal : bool,
+  /// Target state, or `None` for internal transitions
+  pub target    : Option <&'static str>,
+  /// Whether the event was handled in the source state
+  pub covered   : bool
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Action {
+  pub state   : &'static str,
+  /// Whether the action was performed
+  pub covered : bool
+}
+
+impl Report {
+  /// Returns true if all transitions and actions were covered
+  pub fn is_complete (&self) -> bool {
+    self.transitions.iter().all (|t| t.covered) &&
+    self.entry_actions.iter().chain (&self.exit_actions).all (|a| a.covered)
+  }
+
+  pub fn uncovered_transitions (&self)
+    -> impl Iterator <Item=&Transition>
+  {
+    self.transitions.iter().filter (|t| !t.covered)
+  }
+
+  /// Combine with the coverage of another instance of the same machine
+  ///
+  /// # Panics
+  ///
+  /// Panics if the reports are for different machines.
+  pub fn merge (&mut self, other : &Self) {
+    assert_eq!(self.machine, other.machine, "coverage of different machines");
+    for (t, o) in self.transitions.iter_mut().zip (&other.transitions) {
+      t.covered |= o.covered;
+    }
+    for (a, o) in self.entry_actions.iter_mut().zip (&other.entry_actions) {
+      a.covered |= o.covered;
+    }
+    for (a, o) in self.exit_actions.iter_mut().zip (&other.exit_actions) {
+      a.covered |= o.covered;
+    }
+  }
+}
+
+impl std::fmt::Display for Transition {
+  fn fmt (&self, f : &mut std::fmt::Formatter) -> std::fmt::Result {
+    let source = if self.universal { "*" } else { self.source };
+    write!(f, "{} <{source}>", self.event)?;
+    if let Some (target) = self.target {
+      write!(f, " => <{target}>")?;
+    }
+    if self.universal {
+      write!(f, " from <{}>", self.source)?;
+    }
+    Ok (())
+  }
+}
+
+impl std::fmt::Display for Report {
+  fn fmt (&self, f : &mut std::fmt::Formatter) -> std::fmt::Result {
+    let count = |covered : &mut dyn Iterator <Item=bool>| {
+      let (total, covered) = covered.fold ((0, 0),
+        |(total, count), covered| (total + 1, count + usize::from (covered)));
+      format!("{covered}/{total}")
+    };
+    writeln!(f, "{} coverage: {} transitions, {} entry actions, {} exit actions",
+      self.machine,
+      count (&mut self.transitions.iter().map (|t| t.covered)),
+      count (&mut self.entry_actions.iter().map (|a| a.covered)),
+      count (&mut self.exit_actions.iter().map (|a| a.covered)))?;
+    for t in self.uncovered_transitions() {
+      writeln!(f, "  uncovered transition: {t}")?;
+    }
+    for a in self.entry_actions.iter().filter (|a| !a.covered) {
+      writeln!(f, "  uncovered entry action: {}", a.state)?;
+    }
+    for a in self.exit_actions.iter().filter (|a| !a.covered) {
+      writeln!(f, "  uncovered exit action: {}", a.state)?;
+    }
+    Ok (())
+  }
+}
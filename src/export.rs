@@ -0,0 +1,293 @@
+//! Diagram and interchange formats generated from `MachineDotfile` metadata
+
+use crate::MachineDotfile;
+
+/// Transitions of the machine as (event, source, target) names with universal
+/// events expanded to a transition from each state and internal events as
+/// transitions from the source state to itself
+fn transitions <M : MachineDotfile> ()
+  -> Vec <(&'static str, &'static str, &'static str)>
+{
+  let states  = M::states();
+  let sources = M::event_sources();
+  let targets = M::event_targets();
+  let mut transitions = Vec::new();
+  for (i, event) in M::events().into_iter().enumerate() {
+    let target = if targets[i].is_empty() { sources[i] } else { targets[i] };
+    if sources[i] == "*" {
+      transitions.extend (states.iter().map (|source| (event, *source, target)));
+    } else {
+      transitions.push ((event, sources[i], target));
+    }
+  }
+  transitions
+}
+
+/// Mermaid `stateDiagram-v2` text
+pub(crate) fn mermaid <M : MachineDotfile> () -> String {
+  use std::fmt::Write;
+  let mut s = String::new();
+  writeln!(s, "---\ntitle: {}\n---\nstateDiagram-v2", M::name()).unwrap();
+  for state in M::states() {
+    writeln!(s, "  state {state}").unwrap();
+  }
+  writeln!(s, "  [*] --> {}", M::state_initial()).unwrap();
+  for (event, source, target) in transitions::<M>() {
+    writeln!(s, "  {source} --> {target} : {event}").unwrap();
+  }
+  let state_terminal = M::state_terminal();
+  if !state_terminal.is_empty() {
+    writeln!(s, "  {state_terminal} --> [*]").unwrap();
+  }
+  s
+}
+
+/// `PlantUML` state diagram text
+pub(crate) fn plantuml <M : MachineDotfile> () -> String {
+  use std::fmt::Write;
+  let mut s = String::new();
+  writeln!(s, "@startuml\ntitle {}", M::name()).unwrap();
+  let extended_state_names = M::extended_state_names();
+  if !extended_state_names.is_empty() {
+    s.push_str ("note as ExtendedState\n");
+    for (name, typ) in extended_state_names.iter()
+      .zip (M::extended_state_types())
+    {
+      writeln!(s, "  {name} : {typ}").unwrap();
+    }
+    s.push_str ("end note\n");
+  }
+  let state_data_names = M::state_data_names();
+  let state_data_types = M::state_data_types();
+  for (i, state) in M::states().into_iter().enumerate() {
+    writeln!(s, "state {state}").unwrap();
+    for (name, typ) in state_data_names[i].iter().zip (&state_data_types[i]) {
+      writeln!(s, "{state} : {name} : {typ}").unwrap();
+    }
+  }
+  writeln!(s, "[*] --> {}", M::state_initial()).unwrap();
+  for (event, source, target) in transitions::<M>() {
+    writeln!(s, "{source} --> {target} : {event}").unwrap();
+  }
+  let state_terminal = M::state_terminal();
+  if !state_terminal.is_empty() {
+    writeln!(s, "{state_terminal} --> [*]").unwrap();
+  }
+  s.push_str ("@enduml\n");
+  s
+}
+
+/// W3C SCXML document.
+///
+/// Internal events are targetless transitions and universal events are
+/// transitions of a compound state that contains all states. The compound
+/// state has the ID `<Machine>-universal`, which can not collide with a state
+/// name since it is not an identifier. The terminal state is a top-level
+/// `<final>` element if no events other than universal events leave it, so
+/// universal events are not taken from the terminal state.
+pub(crate) fn scxml <M : MachineDotfile> () -> String {
+  use std::fmt::Write;
+  let name           = M::name();
+  let states         = M::states();
+  let events         = M::events();
+  let sources        = M::event_sources();
+  let targets        = M::event_targets();
+  let actions        = M::event_actions();
+  let state_initial  = M::state_initial();
+  let state_terminal = M::state_terminal();
+  let universal      = sources.contains (&"*");
+  let wrapper        = format!("{name}-universal");
+  let transition = |s : &mut String, indent : &str, i : usize| {
+    write!(s, "{indent}<transition event=\"{}\"", events[i]).unwrap();
+    if !targets[i].is_empty() {
+      write!(s, " target=\"{}\"", targets[i]).unwrap();
+    }
+    match actions[i] {
+      "" | "{}" | "{ }" => s.push_str ("/>\n"),
+      action => writeln!(s, ">\n{indent}  <script>{}</script>\n\
+        {indent}</transition>", xml_escape (action)).unwrap()
+    }
+  };
+  let mut s = String::new();
+  writeln!(s, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>\n\
+    <scxml xmlns=\"http://www.w3.org/2005/07/scxml\" version=\"1.0\" \
+    name=\"{name}\" initial=\"{}\">",
+    if universal { wrapper.as_str() } else { state_initial }).unwrap();
+  let indent = if universal {
+    writeln!(s, "  <state id=\"{wrapper}\" initial=\"{state_initial}\">")
+      .unwrap();
+    "    "
+  } else {
+    "  "
+  };
+  let mut terminal_final = false;
+  for state in states {
+    let outgoing : Vec <usize> = (0..events.len())
+      .filter (|i| sources[*i] == state).collect();
+    if state == state_terminal && outgoing.is_empty() {
+      // a final state nested in the compound state would not end the machine
+      terminal_final = true;
+      if !universal {
+        writeln!(s, "{indent}<final id=\"{state}\"/>").unwrap();
+      }
+    } else if outgoing.is_empty() {
+      writeln!(s, "{indent}<state id=\"{state}\"/>").unwrap();
+    } else {
+      writeln!(s, "{indent}<state id=\"{state}\">").unwrap();
+      for i in outgoing {
+        transition (&mut s, &format!("{indent}  "), i);
+      }
+      writeln!(s, "{indent}</state>").unwrap();
+    }
+  }
+  if universal {
+    for i in (0..events.len()).filter (|i| sources[*i] == "*") {
+      transition (&mut s, indent, i);
+    }
+    s.push_str ("  </state>\n");
+    if terminal_final {
+      writeln!(s, "  <final id=\"{state_terminal}\"/>").unwrap();
+    }
+  }
+  s.push_str ("</scxml>\n");
+  s
+}
+
+/// Escape XML special characters
+fn xml_escape (s : &str) -> String {
+  s.replace ('&', "&amp;").replace ('<', "&lt;").replace ('>', "&gt;")
+    .replace ('"', "&quot;")
+}
+
+/// JSON value for the machine description
+enum Json {
+  Null,
+  Number (u32),
+  String (String),
+  Array  (Vec <Json>),
+  Object (Vec <(&'static str, Json)>)
+}
+
+impl Json {
+  fn string (s : &str) -> Self {
+    Json::String (s.to_string())
+  }
+
+  /// A string or null if empty
+  fn optional (s : &str) -> Self {
+    if s.is_empty() { Json::Null } else { Json::string (s) }
+  }
+
+  /// Pretty print with two space indentation
+  fn write (&self, s : &mut String, indent : usize) {
+    use std::fmt::Write;
+    let pad = |s : &mut String, indent : usize|
+      s.extend (std::iter::repeat_n ("  ", indent));
+    match self {
+      Json::Null        => s.push_str ("null"),
+      Json::Number (n)  => write!(s, "{n}").unwrap(),
+      Json::String (v)  => json_escape (s, v),
+      Json::Array  (vs) if vs.is_empty() => s.push_str ("[]"),
+      Json::Object (fs) if fs.is_empty() => s.push_str ("{}"),
+      Json::Array  (vs) => {
+        s.push_str ("[\n");
+        for (i, v) in vs.iter().enumerate() {
+          pad (s, indent + 1);
+          v.write (s, indent + 1);
+          s.push_str (if i + 1 < vs.len() { ",\n" } else { "\n" });
+        }
+        pad (s, indent);
+        s.push (']');
+      }
+      Json::Object (fs) => {
+        s.push_str ("{\n");
+        for (i, (name, v)) in fs.iter().enumerate() {
+          pad (s, indent + 1);
+          json_escape (s, name);
+          s.push_str (": ");
+          v.write (s, indent + 1);
+          s.push_str (if i + 1 < fs.len() { ",\n" } else { "\n" });
+        }
+        pad (s, indent);
+        s.push ('}');
+      }
+    }
+  }
+}
+
+/// JSON description of the machine structure; see `JSON_SCHEMA_VERSION`
+pub(crate) fn json <M : MachineDotfile> () -> String {
+  let variables = |names : Vec <&'static str>, types : Vec <&'static str>,
+    defaults : Vec <&'static str>
+  | Json::Array (names.into_iter().zip (types).zip (defaults)
+    .map (|((name, ty), default)| Json::Object (vec![
+      ("name",    Json::string (name)),
+      ("type",    Json::string (ty)),
+      ("default", Json::optional (default))
+    ])).collect());
+  let type_vars = M::type_vars().into_iter()
+    .map (Json::String).collect();
+  let states = M::states().into_iter()
+    .zip (M::state_data_names().into_iter()
+      .zip (M::state_data_types()).zip (M::state_data_defaults()))
+    .zip (M::state_entry_actions().into_iter().zip (M::state_exit_actions()))
+    .map (|((name, ((names, types), defaults)), (entry, exit))|
+      Json::Object (vec![
+        ("name",         Json::string (name)),
+        ("data",         variables (names, types, defaults)),
+        ("entry_action", Json::optional (entry)),
+        ("exit_action",  Json::optional (exit))
+      ])).collect();
+  let events = M::events().into_iter()
+    .zip (M::event_sources().into_iter().zip (M::event_targets()))
+    .zip (M::event_param_names().into_iter()
+      .zip (M::event_param_types()).zip (M::event_param_defaults()))
+    .zip (M::event_actions())
+    .map (|(((name, (source, target)), ((names, types), defaults)), action)|
+      Json::Object (vec![
+        ("name",   Json::string (name)),
+        ("source",
+          if source == "*" { Json::Null } else { Json::string (source) }),
+        ("target", Json::optional (target)),
+        ("params", variables (names, types, defaults)),
+        ("action", Json::optional (action))
+      ])).collect();
+  let json = Json::Object (vec![
+    ("schema_version", Json::Number (crate::JSON_SCHEMA_VERSION)),
+    ("name",           Json::string (M::name())),
+    ("type_vars",      Json::Array (type_vars)),
+    ("self_reference", Json::optional (M::self_reference())),
+    ("extended_state", variables (M::extended_state_names(),
+      M::extended_state_types(), M::extended_state_defaults())),
+    ("states",         Json::Array (states)),
+    ("initial_state",  Json::string (M::state_initial())),
+    ("initial_action", Json::optional (M::initial_action())),
+    ("terminal_state", Json::optional (M::state_terminal())),
+    ("terminate_success_action",
+      Json::optional (M::terminate_success_action())),
+    ("terminate_failure_action",
+      Json::optional (M::terminate_failure_action())),
+    ("events",         Json::Array (events))
+  ]);
+  let mut s = String::new();
+  json.write (&mut s, 0);
+  s.push ('\n');
+  s
+}
+
+/// Append a quoted and escaped JSON string
+fn json_escape (s : &mut String, v : &str) {
+  use std::fmt::Write;
+  s.push ('"');
+  for c in v.chars() {
+    match c {
+      '"'  => s.push_str ("\\\""),
+      '\\' => s.push_str ("\\\\"),
+      '\n' => s.push_str ("\\n"),
+      '\t' => s.push_str ("\\t"),
+      c if c.is_control() => write!(s, "\\u{:04x}", u32::from (c)).unwrap(),
+      c => s.push (c)
+    }
+  }
+  s.push ('"');
+}
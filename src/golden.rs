@@ -0,0 +1,112 @@
+//! Golden file snapshot testing of generated DOT files.
+//!
+//! `assert_dotfiles` compares the DOT files generated with several
+//! `DotfileOptions` against files checked in to a directory and panics with a
+//! line diff on mismatch. Setting the `MACRO_MACHINES_UPDATE_GOLDEN` environment
+//! variable writes the generated files instead, so that changes to the diagrams
+//! show up in review:
+//!
+//! ```text
+//! #[test]
+//! fn door_dotfiles() {
+//!   golden::assert_dotfiles::<Door> (Path::new (
+//!     concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden")));
+//! }
+//! ```
+//!
+//! ```text
+//! $ MACRO_MACHINES_UPDATE_GOLDEN=1 cargo test
+//! ```
+
+use std::path::Path;
+
+use crate::{Defaults, DotfileOptions, MachineDotfile};
+
+/// Environment variable that causes golden files to be written instead of
+/// compared
+pub const UPDATE_VAR : &str = "MACRO_MACHINES_UPDATE_GOLDEN";
+
+/// Compare the DOT files generated for machine `M` with the golden files
+/// `<name>.dot`, `<name>-show-defaults.dot`, `<name>-pretty-defaults.dot` and
+/// `<name>-hide-actions.dot` in directory `dir`, where `<name>` is the machine
+/// name, generated with the default options, with default expressions, with
+/// pretty printed defaults and without actions respectively
+///
+/// # Panics
+///
+/// Panics if a generated file differs from its golden file, or if updating
+/// the golden files fails.
+pub fn assert_dotfiles <M : MachineDotfile> (dir : &Path) {
+  let name = M::name();
+  assert_matches (dir.join (format!("{name}.dot")), &M::dotfile());
+  assert_matches (dir.join (format!("{name}-show-defaults.dot")),
+    &M::dotfile_with (&DotfileOptions::new().defaults (Defaults::Expression)));
+  assert_matches (dir.join (format!("{name}-pretty-defaults.dot")),
+    &M::dotfile_with (&DotfileOptions::new().defaults (Defaults::Pretty)));
+  assert_matches (dir.join (format!("{name}-hide-actions.dot")),
+    &M::dotfile_with (&DotfileOptions::new().actions (false)));
+}
+
+/// Compare `actual` with the contents of the golden file at `path`, or write
+/// it to the file if the `MACRO_MACHINES_UPDATE_GOLDEN` environment variable
+/// is set
+///
+/// # Panics
+///
+/// Panics with a line diff if the contents differ or the file does not exist,
+/// or if updating the golden file fails.
+pub fn assert_matches <P : AsRef <Path>> (path : P, actual : &str) {
+  let path = path.as_ref();
+  if std::env::var_os (UPDATE_VAR).is_some() {
+    if let Some (parent) = path.parent() {
+      std::fs::create_dir_all (parent).unwrap_or_else (|err|
+        panic!("error creating directory {}: {err}", parent.display()));
+    }
+    std::fs::write (path, actual).unwrap_or_else (|err|
+      panic!("error writing golden file {}: {err}", path.display()));
+    return
+  }
+  let expected = match std::fs::read_to_string (path) {
+    Ok (expected) => expected,
+    Err (err) => panic!("error reading golden file {}: {err}\n\
+      set {UPDATE_VAR}=1 to create it", path.display())
+  };
+  assert!(expected == actual, "golden file {} does not match:\n{}\
+    set {UPDATE_VAR}=1 to update it", path.display(), diff (&expected, actual));
+}
+
+/// Line diff of the expected and actual text, showing removed lines with `-`
+/// and added lines with `+` prefixed by the line number in the respective text
+pub fn diff (expected : &str, actual : &str) -> String {
+  use std::fmt::Write;
+  let expected : Vec <&str> = expected.lines().collect();
+  let actual   : Vec <&str> = actual.lines().collect();
+  // longest common subsequence lengths of the suffixes
+  let mut lcs = vec![vec![0usize; actual.len() + 1]; expected.len() + 1];
+  for i in (0..expected.len()).rev() {
+    for j in (0..actual.len()).rev() {
+      lcs[i][j] = if expected[i] == actual[j] {
+        lcs[i+1][j+1] + 1
+      } else {
+        lcs[i+1][j].max (lcs[i][j+1])
+      };
+    }
+  }
+  let mut s = String::new();
+  let (mut i, mut j) = (0, 0);
+  while i < expected.len() || j < actual.len() {
+    if i < expected.len() && j < actual.len() && expected[i] == actual[j] {
+      i += 1;
+      j += 1;
+    } else if i < expected.len() &&
+      (j == actual.len() || lcs[i+1][j] >= lcs[i][j+1])
+    {
+      writeln!(s, "-{:>4}: {}", i + 1, expected[i]).unwrap();
+      i += 1;
+    } else {
+      writeln!(s, "+{:>4}: {}", j + 1, actual[j]).unwrap();
+      j += 1;
+    }
+  }
+  s
+}
@@ -71,6 +71,79 @@
 //! Initial and terminal actions are always before and after any state entry and
 //! exit actions, respectively.
 //!
+//! State names referenced by events and by the initial and terminal states, as
+//! well as local state variables listed in event actions, are checked at
+//! compile time, as are duplicate state and event names. For example an event
+//! `event Knock <Closed> () { knock_cont } => { ... }` fails with:
+//!
+//! ```text
+//! error: event `Knock`: state `Closed` has no local variable `knock_cont`
+//! ```
+//!
+//! The machine definition is only expanded once these checks pass, so such
+//! mistakes are not followed by errors from the generated code. A duplicate
+//! name is additionally reported by the compiler as a duplicate matcher
+//! binding.
+//!
+//! An optional `logging` clause preceding the `initial_state` sets the `log`
+//! target and the levels of the records emitted by the machine (see
+//! `LogConfig`):
+//!
+//! ```text
+//! logging: {
+//!   target:            "door"
+//!   initialize:        Trace
+//!   accepted:          Debug
+//!   rejected:          Info
+//!   terminate_success: Debug
+//!   terminate_failure: Error
+//! }
+//! ```
+//!
+//! Each field is optional and may be given at most once, in the order shown;
+//! the clause itself may appear at most once. An instance may additionally be
+//! given a name or ID with `set_instance_name` which is included in its log
+//! records.
+//!
+//! Machines defined with the `_debug` macro variants additionally log the
+//! `Debug` values of event parameters and of the current state data before and
+//! after handling each event at trace level. Secret parameters or state fields
+//! may be wrapped in `Redacted` to hide their values from these records.
+//!
+//! In tests, `assert_machine_scenario!` drives a machine through a sequence of
+//! events and checks the state or exception after each:
+//!
+//! ```text
+//! assert_machine_scenario!{ door,
+//!   Knock => Closed,
+//!   Open  => Opened,
+//!   Open  => Err (WrongState)
+//! }
+//! ```
+//!
+//! Event generation for fuzzing and property tests is opted into per machine
+//! with a `fuzz` clause preceding the `initial_state`, listing `arbitrary`
+//! and/or `proptest`, each of which also requires the corresponding optional
+//! feature of this crate:
+//!
+//! ```text
+//! fuzz: arbitrary, proptest
+//! ```
+//!
+//! With `arbitrary`, `arbitrary::Arbitrary` is implemented for the generated
+//! `EventId`, `EventParams` and `Event` types, and with `proptest`
+//! `EventId::strategy()` returns a proptest strategy. For machines defined with
+//! the `_debug` variants `proptest` also generates `Event::strategy()`,
+//! `Event::sequence_strategy (size)` and `EventParams::strategy()`; these are
+//! not available for the other variants since proptest values must implement
+//! `Debug`, and they require `'event : 'static`, so event parameters borrowing
+//! for the `'event` lifetime can not be generated. The types of all event
+//! parameters must implement the respective `Arbitrary` trait.
+//!
+//! With the optional `scxml` feature, `scxml::to_def_machine` and the
+//! `scxml2machine` binary generate a `def_machine!` skeleton from an SCXML
+//! document, e.g. one drawn in a statechart editor.
+//!
 //! The `Door::dotfile()` function will generate a '.dot' file string that can
 //! be saved and rendered as a PNG with layout generated by graphviz `dot` tool:
 //!
@@ -78,16 +151,81 @@
 //! $ dot -Tpng door.dot > door.png
 //! ```
 //!
+//! `Door::dotfile_with()` takes `DotfileOptions` to show default expressions,
+//! hide actions or parameters, or change the layout and styling of the diagram.
+//! `door.dotfile_instance()` generates the diagram of a running instance with
+//! the current state highlighted and the enabled events marked; machines
+//! defined with the `_debug` variants also show the current values of the
+//! extended state and state data.
+//!
 //! ![](https://raw.githubusercontent.com/spearman/macro-machines/master/door.png)
 
 #![cfg_attr(test, allow(dead_code, unreachable_code))]
 
 pub use log;
+#[cfg(feature = "arbitrary")]
+pub use arbitrary;
+#[cfg(feature = "proptest")]
+pub use proptest;
 pub use variant_count::VariantCount;
 
 use marksman_escape;
 
+mod export;
 mod macro_def;
+pub mod analysis;
+pub mod coverage;
+pub mod golden;
+pub mod metrics;
+pub mod paths;
+#[cfg(feature = "scxml")]
+pub mod scxml;
+pub mod walk;
+
+/// Version of the schema of the JSON generated by
+/// `MachineDotfile::machine_json`.
+///
+/// The version is incremented whenever fields are removed or their meaning
+/// changes; fields may be added without changing the version. The schema of
+/// version 1 is:
+///
+/// ```text
+/// {
+///   "schema_version": 1,
+///   "name":           string,
+///   "type_vars":      [string],          // "T = <type name>"
+///   "self_reference": string | null,
+///   "extended_state": [variable],
+///   "states": [{
+///     "name":         string,
+///     "data":         [variable],
+///     "entry_action": string | null,
+///     "exit_action":  string | null
+///   }],
+///   "initial_state":  string,
+///   "initial_action": string | null,
+///   "terminal_state": string | null,
+///   "terminate_success_action": string | null,
+///   "terminate_failure_action": string | null,
+///   "events": [{
+///     "name":   string,
+///     "source": string | null,           // null for universal events
+///     "target": string | null,           // null for internal events
+///     "params": [variable],
+///     "action": string | null
+///   }]
+/// }
+/// ```
+///
+/// where each `variable` is an object with `name` and `type` strings and a
+/// `default` expression string, which is null for event parameters without a
+/// default expression. Actions are the source text of the action blocks.
+///
+/// All source text fields, i.e. the `type` and `default` of variables, the
+/// actions and the `type_vars`, are produced by `stringify!` (and
+/// `std::any::type_name` for type parameters), so their formatting may change
+/// between compiler versions and should not be compared exactly.
+pub const JSON_SCHEMA_VERSION : u32 = 1;
 
 /// Methods for DOT file creation
 // TODO: if we had a proper Machine trait with associated state and event ID
@@ -111,31 +249,258 @@ pub trait MachineDotfile {
   fn event_sources()              -> Vec <&'static str>;
   fn event_targets()              -> Vec <&'static str>;
   fn event_actions()              -> Vec <&'static str>;
+  // provided: these are implemented by the macros, the defaults describe a
+  // machine without event parameters, state entry and exit actions, or
+  // initial and terminal actions
+  /// Names of the parameters of each event
+  fn event_param_names() -> Vec <Vec <&'static str>> {
+    Self::events().iter().map (|_| Vec::new()).collect()
+  }
+  /// Types of the parameters of each event
+  fn event_param_types() -> Vec <Vec <&'static str>> {
+    Self::event_param_names().iter()
+      .map (|names| vec![""; names.len()]).collect()
+  }
+  /// Default expression of each event parameter, or empty if none is given
+  fn event_param_defaults() -> Vec <Vec <&'static str>> {
+    Self::event_param_names().iter()
+      .map (|names| vec![""; names.len()]).collect()
+  }
+  /// Entry action block of each state, or empty if the state has none
+  fn state_entry_actions() -> Vec <&'static str> {
+    vec![""; Self::states().len()]
+  }
+  /// Exit action block of each state, or empty if the state has none
+  fn state_exit_actions() -> Vec <&'static str> {
+    vec![""; Self::states().len()]
+  }
+  /// Initial action block, or empty if none is given
+  fn initial_action() -> &'static str {
+    ""
+  }
+  /// Successful termination action block, or empty if none is given
+  fn terminate_success_action() -> &'static str {
+    ""
+  }
+  /// Failed termination action block, or empty if none is given
+  fn terminate_failure_action() -> &'static str {
+    ""
+  }
   // provided: these are intended to be called by the user
-  /// Generate a DOT file for the state machine that hides default expressions
-  /// for state fields and extended state fields, but shows event actions
+  /// Generate a DOT file for the state machine with the default options:
+  /// default expressions of state fields and extended state fields are hidden,
+  /// all actions and event parameters are shown
   fn dotfile() -> String where Self : Sized {
-    machine_dotfile::<Self> (true, false, false)
+    Self::dotfile_with (&DotfileOptions::default())
+  }
+  /// Generate a DOT file for the state machine with the given options.
+  ///
+  /// &#9888; With `Defaults::Pretty` this function evaluates default
+  /// expressions and pretty prints the resulting values at runtime.
+  fn dotfile_with (options : &DotfileOptions) -> String where Self : Sized {
+    machine_dotfile::<Self> (options, None, None)
   }
   /// Generate a DOT file for the state machine that shows default expressions
   /// for state fields and extended state fields
+  #[deprecated(note = "use `dotfile_with` and `Defaults::Expression`")]
   fn dotfile_show_defaults() -> String where Self : Sized {
-    machine_dotfile::<Self> (false, false, false)
+    Self::dotfile_with (&DotfileOptions::new().defaults (Defaults::Expression))
   }
   /// Generate a DOT file for the state machine that pretty prints the *values*
   /// of default expressions for state fields and extended state fields.
   ///
   /// &#9888; Calling this this function evaluates default expressions and
   /// pretty prints the resulting values at runtime.
+  #[deprecated(note = "use `dotfile_with` and `Defaults::Pretty`")]
   fn dotfile_pretty_defaults() -> String where Self : Sized {
-    machine_dotfile::<Self> (false, true, false)
+    Self::dotfile_with (&DotfileOptions::new().defaults (Defaults::Pretty))
   }
-  /// Do not show event actions
+  /// Do not show event parameters
+  #[deprecated(note = "use `dotfile_with` and `DotfileOptions::params`")]
+  fn dotfile_hide_params() -> String where Self : Sized {
+    Self::dotfile_with (&DotfileOptions::new().params (false))
+  }
+  /// Do not show event actions, state entry and exit actions, or initial and
+  /// terminal actions
+  #[deprecated(note = "use `dotfile_with` and `DotfileOptions::actions`")]
   fn dotfile_hide_actions() -> String where Self : Sized {
-    machine_dotfile::<Self> (true, false, true)
+    Self::dotfile_with (&DotfileOptions::new().actions (false))
+  }
+  /// Generate a Mermaid `stateDiagram-v2` diagram of the state machine, e.g.
+  /// for rendering in a Markdown `mermaid` code block.
+  ///
+  /// Every state is declared, so states without transitions are shown.
+  /// Internal events are shown as transitions from a state to itself and
+  /// universal events as transitions from each state.
+  fn mermaid() -> String where Self : Sized {
+    export::mermaid::<Self>()
+  }
+  /// Generate a `PlantUML` state diagram of the state machine with extended
+  /// state variables in a note and state-local variables as state
+  /// descriptions
+  fn plantuml() -> String where Self : Sized {
+    export::plantuml::<Self>()
+  }
+  /// Generate a W3C SCXML document for the state machine with event actions
+  /// as `<script>` elements
+  fn scxml() -> String where Self : Sized {
+    export::scxml::<Self>()
+  }
+  /// Generate a JSON description of the state machine structure: the
+  /// extended state, states with their local variables and entry and exit
+  /// actions, and events with their parameters, transitions and actions.
+  ///
+  /// The document has a top-level `schema_version` field; see
+  /// `JSON_SCHEMA_VERSION` for the schema.
+  fn machine_json() -> String where Self : Sized {
+    export::json::<Self>()
+  }
+  /// Generate a DOT file for the state machine with the transitions covered by
+  /// the given coverage report of the machine in green and uncovered
+  /// transitions in red; universal transitions are only shown as covered if
+  /// they were taken from every state
+  fn dotfile_coverage (coverage : &coverage::Report) -> String
+    where Self : Sized
+  {
+    machine_dotfile::<Self> (&DotfileOptions::default(), Some (coverage), None)
   }
 }
 
+/// How default expressions of state fields and extended state fields are
+/// shown in DOT files
+#[derive(Clone, Copy, Debug, Default, Eq, PartialEq)]
+pub enum Defaults {
+  #[default]
+  Hidden,
+  /// Show the default expressions
+  Expression,
+  /// Evaluate the default expressions and pretty print the values
+  Pretty
+}
+
+/// Options for DOT file generation with `MachineDotfile::dotfile_with`:
+///
+/// ```text
+/// let dotfile = Door::dotfile_with (&DotfileOptions::new()
+///   .defaults (Defaults::Expression)
+///   .actions (false)
+///   .rankdir ("TB"));
+/// ```
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DotfileOptions {
+  defaults         : Defaults,
+  actions          : bool,
+  params           : bool,
+  entry_exit       : bool,
+  expand_universal : bool,
+  rankdir          : String,
+  font             : String,
+  mono_font        : String,
+  node_color       : Option <String>,
+  edge_color       : Option <String>
+}
+
+impl DotfileOptions {
+  pub fn new() -> Self {
+    Self::default()
+  }
+  /// How default expressions are shown; hidden by default
+  pub const fn defaults (mut self, defaults : Defaults) -> Self {
+    self.defaults = defaults;
+    self
+  }
+  /// Show event actions, state entry and exit actions, and initial and
+  /// terminal actions; shown by default
+  pub const fn actions (mut self, show : bool) -> Self {
+    self.actions = show;
+    self
+  }
+  /// Show event parameters on edges; shown by default
+  pub const fn params (mut self, show : bool) -> Self {
+    self.params = show;
+    self
+  }
+  /// Show state entry and exit actions in state nodes if actions are shown;
+  /// shown by default
+  pub const fn entry_exit (mut self, show : bool) -> Self {
+    self.entry_exit = show;
+    self
+  }
+  /// Draw universal events as an edge from each state instead of from a
+  /// single `*` node; not expanded by default
+  pub const fn expand_universal (mut self, expand : bool) -> Self {
+    self.expand_universal = expand;
+    self
+  }
+  /// Graphviz `rankdir` attribute; `LR` by default
+  pub fn rankdir <S : Into <String>> (mut self, rankdir : S) -> Self {
+    self.rankdir = rankdir.into();
+    self
+  }
+  /// Font family of state, event and machine names; `Sans` by default
+  pub fn font <S : Into <String>> (mut self, font : S) -> Self {
+    self.font = font.into();
+    self
+  }
+  /// Font family of variables, parameters and actions; `Mono` by default
+  pub fn mono_font <S : Into <String>> (mut self, font : S) -> Self {
+    self.mono_font = font.into();
+    self
+  }
+  /// Graphviz color of state nodes
+  pub fn node_color <S : Into <String>> (mut self, color : S) -> Self {
+    self.node_color = Some (color.into());
+    self
+  }
+  /// Graphviz color of transition edges
+  pub fn edge_color <S : Into <String>> (mut self, color : S) -> Self {
+    self.edge_color = Some (color.into());
+    self
+  }
+}
+
+impl Default for DotfileOptions {
+  fn default() -> Self {
+    DotfileOptions {
+      defaults:         Defaults::Hidden,
+      actions:          true,
+      params:           true,
+      entry_exit:       true,
+      expand_universal: false,
+      rankdir:          "LR".to_string(),
+      font:             "Sans".to_string(),
+      mono_font:        "Mono".to_string(),
+      node_color:       None,
+      edge_color:       None
+    }
+  }
+}
+
+/// Static metadata with typed state and event IDs.
+///
+/// Implemented for each generated machine by the `def_machine` macros, with
+/// the generated `StateId` and `EventId` enums as the ID types.
+pub trait MachineMetadata {
+  type StateId : Clone + std::fmt::Debug + Eq + 'static;
+  type EventId : Clone + std::fmt::Debug + Eq + 'static;
+  /// All states in order of definition
+  const STATES         : &'static [Self::StateId];
+  /// All events in order of definition
+  const EVENTS         : &'static [Self::EventId];
+  /// The source and target state of each event in order of definition.
+  ///
+  /// Universal events have no source state and internal events have no
+  /// target state.
+  #[expect(clippy::type_complexity)]
+  const TRANSITIONS    : &'static [
+    (Self::EventId, Option <Self::StateId>, Option <Self::StateId>)
+  ];
+  const STATE_INITIAL  : Self::StateId;
+  const STATE_TERMINAL : Option <Self::StateId>;
+  fn state_name (id : &Self::StateId) -> &'static str;
+  fn event_name (id : &Self::EventId) -> &'static str;
+}
+
 /// Describes an exceptional result when attempting to handle an event.
 ///
 /// Currently the only exception is the '`WrongState`' exception.
@@ -144,25 +509,191 @@ pub enum HandleEventException {
   WrongState
 }
 
+/// The effects of handling an event in the current state of a machine, as
+/// returned by the generated `preview` method
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct TransitionPlan <S, E> {
+  pub event        : E,
+  /// Current state, if its exit action would be performed
+  pub exit_action  : Option <S>,
+  /// The event, if it has an action that would be performed
+  pub event_action : Option <E>,
+  /// Target state, if its entry action would be performed
+  pub entry_action : Option <S>,
+  /// Resulting state
+  pub target       : S
+}
+
+/// Log target and levels used by a state machine.
+///
+/// Each generated machine exposes its configuration as the associated
+/// `LOG_CONFIG` constant. Defaults may be overridden with the optional
+/// `logging` clause of the machine definition, e.g.:
+///
+/// ```text
+/// logging: {
+///   target:   "door"
+///   rejected: Debug
+/// }
+/// ```
+///
+/// Fields that are not given keep their default: the target defaults to the
+/// module path of the machine definition, the `rejected` and
+/// `terminate_failure` levels default to `Warn` and the remaining levels
+/// default to `Debug`.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub struct LogConfig {
+  pub target            : &'static str,
+  /// Level of the record emitted when a machine is created with `initial()`
+  pub initialize        : log::Level,
+  /// Level of records for events that were handled
+  pub accepted          : log::Level,
+  /// Level of records for events that raised a `HandleEventException`
+  pub rejected          : log::Level,
+  /// Level of the record emitted when dropped in the terminal state
+  pub terminate_success : log::Level,
+  /// Level of the record emitted when dropped in a non-terminal state
+  pub terminate_failure : log::Level
+}
+
+/// Wrapper for secret event parameters and state fields that is formatted as
+/// `<redacted>` by `Debug`, e.g. in trace level log records.
+///
+/// The wrapped value is accessible through `Deref` and `DerefMut`:
+///
+/// ```text
+/// event Login <LoggedOut> => <LoggedIn> (password : Redacted <String>)
+///   {} => { check (password.as_str()); }
+/// ```
+#[derive(Clone, Copy, Default, Eq, Hash, Ord, PartialEq, PartialOrd)]
+pub struct Redacted <T> (pub T);
+
+impl <T> Redacted <T> {
+  #[inline]
+  pub fn into_inner (self) -> T {
+    self.0
+  }
+}
+
+impl <T> std::fmt::Debug for Redacted <T> {
+  fn fmt (&self, f : &mut std::fmt::Formatter) -> std::fmt::Result {
+    f.write_str ("<redacted>")
+  }
+}
+
+impl <T> std::ops::Deref for Redacted <T> {
+  type Target = T;
+  #[inline]
+  fn deref (&self) -> &T {
+    &self.0
+  }
+}
+
+impl <T> std::ops::DerefMut for Redacted <T> {
+  #[inline]
+  fn deref_mut (&mut self) -> &mut T {
+    &mut self.0
+  }
+}
+
+impl <T> From <T> for Redacted <T> {
+  #[inline]
+  fn from (value : T) -> Self {
+    Redacted (value)
+  }
+}
+
+#[cfg(feature = "arbitrary")]
+impl <'a, T : arbitrary::Arbitrary <'a>> arbitrary::Arbitrary <'a>
+  for Redacted <T>
+{
+  fn arbitrary (u : &mut arbitrary::Unstructured <'a>)
+    -> arbitrary::Result <Self>
+  {
+    T::arbitrary (u).map (Redacted)
+  }
+}
+
+#[cfg(feature = "proptest")]
+impl <T : proptest::arbitrary::Arbitrary> proptest::arbitrary::Arbitrary
+  for Redacted <T>
+{
+  type Parameters = T::Parameters;
+  type Strategy   = proptest::strategy::Map <T::Strategy, fn (T) -> Self>;
+  fn arbitrary_with (args : Self::Parameters) -> Self::Strategy {
+    use proptest::strategy::Strategy;
+    proptest::arbitrary::any_with::<T> (args).prop_map (Redacted)
+  }
+}
+
+/// Whether the stringified block of an action has any statements, i.e. is not
+/// omitted or `{}`; used by the generated `has_action`, `has_entry_action` and
+/// `has_exit_action` methods
+#[doc(hidden)]
+pub const fn is_action (block : &str) -> bool {
+  !matches!(block.as_bytes(), b"" | b"{}" | b"{ }")
+}
+
+/// Generate a DOT file for a machine instance in state `state` with the
+/// given `Debug` values of the extended state and the current state data, if
+/// known, and whether each event is enabled; used by the generated
+/// `dotfile_instance` method
+#[doc(hidden)]
+pub fn instance_dotfile <M : MachineDotfile> (
+  state          : &str,
+  extended_state : Option <&[String]>,
+  state_data     : Option <&[String]>,
+  enabled        : &[bool]
+) -> String {
+  let instance = Instance { state, extended_state, state_data, enabled };
+  machine_dotfile::<M> (&DotfileOptions::default(), None, Some (&instance))
+}
+
 //
 //  private functions
 //
 
+/// Runtime values of a machine instance shown in its DOT file
+struct Instance <'a> {
+  state          : &'a str,
+  /// Values of the extended state, if known
+  extended_state : Option <&'a [String]>,
+  /// Values of the current state data, if known
+  state_data     : Option <&'a [String]>,
+  enabled        : &'a [bool]
+}
+
 /// Private DOT file creation function
-fn machine_dotfile <M : MachineDotfile>
-  (hide_defaults : bool, pretty_defaults : bool, hide_actions : bool) -> String
-{
+fn machine_dotfile <M : MachineDotfile> (
+  options  : &DotfileOptions,
+  coverage : Option <&coverage::Report>,
+  instance : Option <&Instance>
+) -> String {
+  let hide_defaults   = options.defaults == Defaults::Hidden;
+  let pretty_defaults = options.defaults == Defaults::Pretty;
+  let hide_actions    = !options.actions;
+  // font faces of HTML labels
+  let font            = escape (options.font.clone());
+  let mono            = escape (options.mono_font.clone());
+  let fontname = |style : &str| quote (&format!("{} {style}", options.font));
+  let color = |color : &Option <String>| color.as_ref()
+    .map (|color| format!(", color={0}, fontcolor={0}", quote (color)))
+    .unwrap_or_default();
   let mut s = String::new();
   //
   // begin graph
   //
   // overlap = scale for neato layouts
-  s.push_str (
-    "digraph {\n  \
+  s.push_str (format!(
+    "digraph {{\n  \
        overlap=scale\n  \
-       rankdir=LR\n  \
-       node [shape=record, style=rounded, fontname=\"Sans Bold\"]\n  \
-       edge [fontname=\"Sans\"]\n");
+       rankdir={}\n  \
+       node [shape=record, style=rounded, fontname={}{}]\n  \
+       edge [fontname={}{}]\n",
+    quote (&options.rankdir),
+    fontname ("Bold"), color (&options.node_color),
+    quote (&options.font), color (&options.edge_color)
+  ).as_str());
 
     //
   { // begin subgraph
@@ -190,12 +721,21 @@ fn machine_dotfile <M : MachineDotfile>
   let mut mono_font           = false;
   let extended_state_names    = M::extended_state_names();
   let extended_state_types    = M::extended_state_types();
-  let extended_state_defaults = M::extended_state_defaults();
+  // an instance shows the current values instead of the default expressions
+  let extended_state_values =
+    instance.and_then (|instance| instance.extended_state);
+  let extended_state_defaults : Vec <String> = match extended_state_values {
+    Some (values) => values.to_vec(),
+    None => M::extended_state_defaults().into_iter().map (str::to_string)
+      .collect()
+  };
+  let show_extended_defaults = extended_state_values.is_some() ||
+    !hide_defaults && instance.is_none();
   debug_assert_eq!(extended_state_names.len(), extended_state_types.len());
   debug_assert_eq!(extended_state_types.len(), extended_state_defaults.len());
 
   if !extended_state_names.is_empty() {
-    s.push_str ("<FONT FACE=\"Mono\"><BR/><BR/>\n");
+    s.push_str (format!("<FONT FACE=\"{mono}\"><BR/><BR/>\n").as_str());
     mono_font = true;
     //  for each extended state field, print a line
     // TODO: we are manually aligning the columns of the field name and field
@@ -218,7 +758,7 @@ fn machine_dotfile <M : MachineDotfile>
         std::iter::repeat_n (' ', longest_typename - extended_state_types[i].len())
           .collect();
 
-      if !hide_defaults && !extended_state_defaults[i].is_empty() {
+      if show_extended_defaults && !extended_state_defaults[i].is_empty() {
         extended_string.push_str (escape (format!(
           "{}{} : {}{} = {}",
           f, spacer1, extended_state_types[i], spacer2, extended_state_defaults[i]
@@ -250,10 +790,10 @@ fn machine_dotfile <M : MachineDotfile>
   if mono_font {
     s.push_str ("</FONT><BR/>");
   }
-  s.push_str (">\
+  s.push_str (format!(">\
     \n    shape=record\
     \n    style=rounded\
-    \n    fontname=\"Sans Bold Italic\"\n");
+    \n    fontname={}\n", fontname ("Bold Italic")).as_str());
   } // end begin subgraph
 
   //
@@ -286,6 +826,8 @@ fn machine_dotfile <M : MachineDotfile>
       }).collect()
     ).collect()
   };
+  let state_entry_actions = M::state_entry_actions();
+  let state_exit_actions  = M::state_exit_actions();
   debug_assert_eq!(state_data_names.len(), state_data_types.len());
   debug_assert_eq!(state_data_types.len(), state_data_defaults.len());
 
@@ -294,7 +836,14 @@ fn machine_dotfile <M : MachineDotfile>
     let mut mono_font       = false;
     let state_data_names    = &state_data_names[i];
     let state_data_types    = &state_data_types[i];
-    let state_data_defaults = &state_data_defaults[i];
+    // an instance shows the values of the current state only
+    let current = instance.filter (|instance| instance.state == *state);
+    let (state_data_defaults, show_defaults) =
+      match current.and_then (|instance| instance.state_data)
+    {
+      Some (values) => (values, true),
+      None => (&state_data_defaults[i][..], !hide_defaults && instance.is_none())
+    };
     debug_assert_eq!(state_data_names.len(), state_data_types.len());
     debug_assert_eq!(state_data_types.len(), state_data_defaults.len());
     s.push_str (format!("    {state} [label=<<B>{state}</B>").as_str());
@@ -302,7 +851,7 @@ fn machine_dotfile <M : MachineDotfile>
     // is counted as part of the layout so we don't indent these lines
     if !state_data_names.is_empty() {
       if !mono_font {
-        s.push_str ("|<FONT FACE=\"Mono\"><BR/>\n");
+        s.push_str (format!("|<FONT FACE=\"{mono}\"><BR/>\n").as_str());
         mono_font = true;
       }
       let mut data_string = String::new();
@@ -317,7 +866,7 @@ fn machine_dotfile <M : MachineDotfile>
         let spacer2 : String =
           std::iter::repeat_n (' ', longest_typename - state_data_types[i].len())
             .collect();
-        if !hide_defaults && !state_data_defaults[i].is_empty() {
+        if show_defaults && !state_data_defaults[i].is_empty() {
           data_string.push_str (escape (format!(
             "{}{} : {}{} = {}",
             f, spacer1, state_data_types[i], spacer2, state_data_defaults[i]
@@ -349,7 +898,29 @@ fn machine_dotfile <M : MachineDotfile>
     if mono_font {
       s.push_str ("<BR ALIGN=\"LEFT\"/></FONT>");
     }
-    s.push_str (">]\n");
+
+    // entry and exit actions
+    if options.actions && options.entry_exit {
+      let actions : Vec <String> = [
+        ("entry", state_entry_actions[i]), ("exit", state_exit_actions[i])
+      ].into_iter().filter (|(_, action)| is_action (action))
+        .map (|(kind, action)|
+          escape (format!("{kind} / {}", single_spaced (action))))
+        .collect();
+      if !actions.is_empty() {
+        s.push_str (format!("|<FONT FACE=\"{mono}\">").as_str());
+        for action in actions {
+          s.push_str (action.as_str());
+          s.push_str ("<BR ALIGN=\"LEFT\"/>");
+        }
+        s.push_str ("</FONT>");
+      }
+    }
+    s.push ('>');
+    if current.is_some() {
+      s.push_str (", style=\"rounded,filled\", fillcolor=lightblue");
+    }
+    s.push_str ("]\n");
   } // end for each state: node
   // end nodes (states)
 
@@ -357,32 +928,49 @@ fn machine_dotfile <M : MachineDotfile>
   // transitions (events)
   //
   // initial transition edge
-  // TODO: show initial action
-  s.push_str (format!(
-    "    INITIAL -> {}\n", M::state_initial()).as_str());
+  s.push_str (format!("    INITIAL -> {}", M::state_initial()).as_str());
+  let initial_action = M::initial_action();
+  if !hide_actions && is_action (initial_action) {
+    s.push_str (format!(" [label=<<FONT FACE=\"{mono}\">{}</FONT>>]",
+      escape (single_spaced (initial_action))).as_str());
+  }
+  s.push ('\n');
   let event_sources = M::event_sources();
   let event_targets = M::event_targets();
   let event_actions = M::event_actions();
+  let event_param_names    = M::event_param_names();
+  let event_param_types    = M::event_param_types();
+  let event_param_defaults = M::event_param_defaults();
+  let states = M::states();
   let mut universal = false;
   // for each event: transition edge
   for (i, event) in M::events().into_iter().enumerate() {
     let source = event_sources[i];
-    let mut target = event_targets[i];
+    let target = event_targets[i];
     let action = event_actions[i];
-    if target.is_empty() {  // internal transition source == target
-      target = source;
-    }
-
-    if source == "*" {
+    let sources = if source == "*" && options.expand_universal {
+      states.clone()
+    } else {
+      vec![source]
+    };
+    if source == "*" && !options.expand_universal {
       universal = true;
     }
-    s.push_str (format!(
-      "    \"{source}\" -> \"{target}\" [label=<<FONT FACE=\"Sans Italic\">{event}</FONT>"
-    ).as_str());
+    let mut label = format!("<FONT FACE=\"{font} Italic\">{event}</FONT>");
 
-    let mut mono_font = false;
     // params
-    // TODO
+    let mut mono_font = options.params && !event_param_names[i].is_empty();
+    if mono_font {
+      let params : Vec <String> = event_param_names[i].iter()
+        .zip (&event_param_types[i]).zip (&event_param_defaults[i])
+        .map (|((name, ty), default)| if !hide_defaults && !default.is_empty() {
+          format!("{name} : {ty} = {default}")
+        } else {
+          format!("{name} : {ty}")
+        }).collect();
+      label.push_str (format!("<FONT FACE=\"{mono}\">").as_str());
+      label.push_str (escape (format!("({})", params.join (", "))).as_str());
+    }
     // guards
     // TODO
 
@@ -391,51 +979,73 @@ fn machine_dotfile <M : MachineDotfile>
         // don't render empty actions
         "{}" | "{ }" => {}
         _ => {
-          if !mono_font {
-            s.push_str ("<FONT FACE=\"Mono\"><BR/>");
+          if mono_font {
+            label.push_str ("<BR/>");
+          } else {
+            label.push_str (format!("<FONT FACE=\"{mono}\"><BR/>").as_str());
             mono_font = true;
           }
-          // replace whitespace with single spaces
-          let action_string = {
-            let mut s : String = action.split_whitespace().map (
-              |s| {
-                let mut s = s.to_string();
-                s.push (' ');
-                s
-              }
-            ).collect();
-            assert_eq!(s.pop(), Some (' '));
-            s
-          };
-          // TODO: different formatting if params or guards were present
-          //action = "  ".to_string() + action.as_str();
-          s.push_str (escape (action_string).as_str());
+          label.push_str (escape (single_spaced (action)).as_str());
         }
       }
     }
 
     if mono_font {
-      s.push_str ("</FONT>");
+      label.push_str ("</FONT>");
+    }
+    let enabled = instance.is_some_and (|instance| instance.enabled[i]);
+    for source in sources {
+      let mut attributes = coverage.map (|coverage| {
+        // an edge from "*" is covered only if taken from every state
+        let covered = coverage.transitions.iter()
+          .filter (|t|
+            t.event == event && (source == "*" || t.source == source))
+          .all (|t| t.covered);
+        let color = if covered { "green" } else { "red" };
+        format!(", color={color}, fontcolor={color}")
+      }).unwrap_or_default();
+      if enabled {
+        attributes.push_str (", color=blue, fontcolor=blue, penwidth=2");
+      }
+      // internal transition source == target
+      let target = if target.is_empty() { source } else { target };
+      s.push_str (format!(
+        "    \"{source}\" -> \"{target}\" [label=<{label}>{attributes}]\n"
+      ).as_str());
     }
-    s.push_str (">]\n");
   } // end for each event: transition edge
 
   if universal {
-    for state in M::states() {
+    for state in states {
       s.push_str (format!(
-        "    {state} -> \"*\" [style=dashed, color=gray]").as_str());
+        "    {state} -> \"*\" [style=dashed, color=gray]\n").as_str());
     }
   }
 
   // terminal transition: node + edge
-  // TODO: show terminal action(s)
   let state_terminal = M::state_terminal();
   if !state_terminal.is_empty() {
     s.push_str (
       "    TERMINAL [label=\"\", shape=doublecircle, width=0.2,\
      \n      style=filled, fillcolor=black]\n");
-    s.push_str (format!(
-      "    {state_terminal} -> TERMINAL\n").as_str());
+    s.push_str (format!("    {state_terminal} -> TERMINAL").as_str());
+    let actions : Vec <String> = [
+      ("success", M::terminate_success_action()),
+      ("failure", M::terminate_failure_action())
+    ].into_iter().filter (|(_, action)|
+      !hide_actions && is_action (action))
+      .map (|(kind, action)|
+        escape (format!("{kind} / {}", single_spaced (action))))
+      .collect();
+    if !actions.is_empty() {
+      s.push_str (format!(" [label=<<FONT FACE=\"{mono}\">").as_str());
+      for action in actions {
+        s.push_str (action.as_str());
+        s.push_str ("<BR ALIGN=\"LEFT\"/>");
+      }
+      s.push_str ("</FONT>>]");
+    }
+    s.push ('\n');
   }
   // end transitions
 
@@ -455,6 +1065,17 @@ fn escape (s : String) -> String {
   String::from_utf8 (Escape::new (s.bytes()).collect()).unwrap()
 }
 
+/// Quote a DOT attribute value, where double quotes are the only escaped
+/// characters
+fn quote (s : &str) -> String {
+  format!("\"{}\"", s.replace ('"', "\\\""))
+}
+
+/// Replace whitespace with single spaces
+fn single_spaced (s : &str) -> String {
+  s.split_whitespace().collect::<Vec <_>>().join (" ")
+}
+
 #[cfg(doc)]
 pub mod example {
   //! Example generated state machine
@@ -490,6 +1111,8 @@ pub mod example {
 #[cfg(test)]
 mod tests {
   #![expect(unreachable_pub)]
+  // extended state tables of the debug machines are built with `push`
+  #![expect(clippy::vec_init_then_push)]
 
   use super::*;
   #[test]
@@ -664,4 +1287,1330 @@ mod tests {
       assert_eq!(test.state_id(), StateId::B);
     }
   }
+  #[test]
+  fn logging() {
+    {
+      def_machine!{
+        Test () {
+          STATES [ state A () ]
+          EVENTS [ ]
+          initial_state: A
+        }
+      }
+      assert_eq!(Test::LOG_CONFIG, LogConfig {
+        target:            module_path!(),
+        initialize:        log::Level::Debug,
+        accepted:          log::Level::Debug,
+        rejected:          log::Level::Warn,
+        terminate_success: log::Level::Debug,
+        terminate_failure: log::Level::Warn
+      });
+      let mut test = Test::initial();
+      assert_eq!(test.instance_name(), None);
+      test.set_instance_name ("test-0".to_string());
+      assert_eq!(test.instance_name(), Some ("test-0"));
+    } {
+      def_machine_nodefault_debug!{
+        Test () {
+          STATES [ state A () ]
+          EVENTS [ event E <A> () ]
+          logging: {
+            target:   "test"
+            rejected: Debug
+            terminate_failure: Error
+          }
+          initial_state: A
+        }
+      }
+      assert_eq!(Test::LOG_CONFIG, LogConfig {
+        target:            "test",
+        initialize:        log::Level::Debug,
+        accepted:          log::Level::Debug,
+        rejected:          log::Level::Debug,
+        terminate_success: log::Level::Debug,
+        terminate_failure: log::Level::Error
+      });
+    }
+  }
+  #[test]
+  fn redacted() {
+    use std::sync::Mutex;
+    // captures the records of the test target with their key-value pairs
+    type Pairs = Vec <(String, String)>;
+    struct Capture (Mutex <Vec <(String, Pairs)>>);
+    impl log::Log for Capture {
+      fn enabled (&self, metadata : &log::Metadata) -> bool {
+        metadata.target() == "redacted"
+      }
+      fn log (&self, record : &log::Record) {
+        struct Visitor (Pairs);
+        impl <'kvs> log::kv::VisitSource <'kvs> for Visitor {
+          fn visit_pair (&mut self, key : log::kv::Key <'kvs>,
+            value : log::kv::Value <'kvs>
+          ) -> Result <(), log::kv::Error> {
+            self.0.push ((key.to_string(), value.to_string()));
+            Ok (())
+          }
+        }
+        if self.enabled (record.metadata()) {
+          let mut pairs = Visitor (vec![]);
+          record.key_values().visit (&mut pairs).unwrap();
+          self.0.lock().unwrap().push ((record.args().to_string(), pairs.0));
+        }
+      }
+      fn flush (&self) { }
+    }
+    static CAPTURE : Capture = Capture (Mutex::new (vec![]));
+    def_machine_debug!{
+      Test () {
+        STATES [
+          state A ()
+          state B (token : Redacted <String>)
+        ]
+        EVENTS [
+          event E <A> => <B> (password : Redacted <String>)
+        ]
+        logging: {
+          target: "redacted"
+        }
+        initial_state: A
+      }
+    }
+    log::set_logger (&CAPTURE).unwrap();
+    log::set_max_level (log::LevelFilter::Trace);
+    let password = Redacted ("hunter2".to_string());
+    assert_eq!(password.as_str(), "hunter2");
+    let event = Event::from (EventParams::E { password });
+    assert_eq!(format!("{event:?}"),
+      "Event { id: E, params: E { password: <redacted> } }");
+    let mut test = Test::initial();
+    test.handle_event (event).unwrap();
+    assert_eq!(format!("{:?}", test.state_data()),
+      "B { token: <redacted> }");
+    let records = CAPTURE.0.lock().unwrap().clone();
+    let find = |message : &str| records.iter()
+      .find (|(m, _)| m == message).map (|(_, pairs)| pairs.clone()).unwrap();
+    let pair = |key : &str, value : &str| (key.to_string(), value.to_string());
+    let params = find ("handle state machine event parameters");
+    assert!(params.contains (&pair ("machine", "Test")));
+    assert!(params.contains (&pair ("params", "E { password: <redacted> }")));
+    assert!(params.contains (&pair ("data", "A")));
+    let result = find ("handle state machine event result");
+    assert!(result.contains (&pair ("state", "B")));
+    assert!(result.contains (&pair ("data", "B { token: <redacted> }")));
+    assert!(records.iter().all (|(_, pairs)|
+      pairs.iter().all (|(_, value)| !value.contains ("hunter2"))));
+  }
+  #[test]
+  fn metrics() {
+    use std::sync::Arc;
+    use std::sync::atomic::{AtomicU64, Ordering};
+    use std::time::Duration;
+    def_machine!{
+      Test () {
+        STATES [
+          state A ()
+          state B ()
+        ]
+        EVENTS [
+          event E <A> => <B> ()
+          event F <B> => <A> ()
+        ]
+        initial_state: A
+      }
+    }
+    let millis = Arc::new (AtomicU64::new (0));
+    let clock = {
+      let millis = millis.clone();
+      move || Duration::from_millis (millis.load (Ordering::SeqCst))
+    };
+    let mut test = Test::initial();
+    assert!(test.metrics().is_none());
+    test.set_instance_name ("t0".to_string());
+    test.enable_metrics (Box::new (clock));
+    millis.store (100, Ordering::SeqCst);
+    test.handle_event (EventId::E.into()).unwrap();
+    test.handle_event (EventId::E.into()).unwrap_err();
+    millis.store (350, Ordering::SeqCst);
+    let snapshot = test.metrics().unwrap();
+    assert_eq!(snapshot.events, vec![
+      metrics::EventStats { event: "E", accepted: 1, rejected: 1 },
+      metrics::EventStats { event: "F", accepted: 0, rejected: 0 }
+    ]);
+    assert_eq!(snapshot.events[0].rejection_rate(), 0.5);
+    assert_eq!(snapshot.states, vec![
+      metrics::StateStats {
+        state: "A", entries: 1, time: Duration::from_millis (100), current: false
+      },
+      metrics::StateStats {
+        state: "B", entries: 1, time: Duration::from_millis (250), current: true
+      }
+    ]);
+    let prometheus = snapshot.to_prometheus();
+    assert!(prometheus.contains ("# TYPE macro_machines_event_rejected_total counter\n\
+      macro_machines_event_rejected_total{machine=\"Test\",instance=\"t0\",event=\"E\"} 1\n"));
+    assert!(prometheus.contains (
+      "macro_machines_state_seconds_total{machine=\"Test\",instance=\"t0\",state=\"B\"} 0.25\n"));
+    let other = metrics::Snapshot { instance: Some ("t1".to_string()), .. snapshot.clone() };
+    let prometheus = metrics::to_prometheus (&[snapshot, other]);
+    assert_eq!(prometheus.matches ("# HELP macro_machines_state_current ").count(), 1);
+    assert_eq!(prometheus.matches ("# TYPE macro_machines_state_current ").count(), 1);
+    assert!(prometheus.contains ("# TYPE macro_machines_state_current gauge\n\
+      macro_machines_state_current{machine=\"Test\",instance=\"t0\",state=\"A\"} 0\n\
+      macro_machines_state_current{machine=\"Test\",instance=\"t0\",state=\"B\"} 1\n\
+      macro_machines_state_current{machine=\"Test\",instance=\"t1\",state=\"A\"} 0\n\
+      macro_machines_state_current{machine=\"Test\",instance=\"t1\",state=\"B\"} 1\n"));
+    test.disable_metrics();
+    assert!(test.metrics().is_none());
+  }
+  #[test]
+  fn metadata() {
+    def_machine_nodefault!{
+      Test () {
+        STATES [
+          state A ()
+          state B ()
+        ]
+        EVENTS [
+          event E <A> => <B> ()
+          event F <B> ()
+          event G <*> => <A> ()
+        ]
+        initial_state: A
+        terminal_state: B
+      }
+    }
+    assert_eq!(StateId::ALL, &[StateId::A, StateId::B]);
+    assert_eq!(EventId::ALL, &[EventId::E, EventId::F, EventId::G]);
+    assert_eq!(StateId::B.name(), "B");
+    assert_eq!(EventId::G.name(), "G");
+    assert_eq!(Test::TRANSITIONS, &[
+      (EventId::E, Some (StateId::A), Some (StateId::B)),
+      (EventId::F, Some (StateId::B), None),
+      (EventId::G, None, Some (StateId::A))
+    ]);
+    assert_eq!(Test::STATE_INITIAL, StateId::A);
+    assert_eq!(Test::STATE_TERMINAL, Some (StateId::B));
+    assert_eq!(Test::event_name (&EventId::F), "F");
+  }
+  #[test]
+  fn enabled_events() {
+    def_machine!{
+      Test () {
+        STATES [
+          state A ()
+          state B ()
+        ]
+        EVENTS [
+          event E <A> => <B> ()
+          event F <B> ()
+          event G <*> => <A> ()
+        ]
+        initial_state: A
+      }
+    }
+    let mut test = Test::initial();
+    assert!(test.can_handle (&EventId::E));
+    assert!(!test.can_handle (&EventId::F));
+    assert_eq!(test.enabled_events().collect::<Vec <_>>(),
+      vec![EventId::E, EventId::G]);
+    test.handle_event (EventId::E.into()).unwrap();
+    assert_eq!(test.enabled_events().collect::<Vec <_>>(),
+      vec![EventId::F, EventId::G]);
+  }
+  #[test]
+  fn preview() {
+    def_machine!{
+      Test (count : u64) {
+        STATES [
+          state A () {
+            exit { *count += 1; }
+          }
+          state B () {
+            entry { *count += 1; }
+            exit  { }
+          }
+        ]
+        EVENTS [
+          event E <A> => <B> () {} => { *count += 1; }
+          event F <B> () {} => { *count += 1; }
+          event G <*> => <A> ()
+          event H <A> () {} => {}
+        ]
+        initial_state: A
+      }
+    }
+    let mut test = Test::initial();
+    assert_eq!(test.preview (&EventId::E.into()), Ok (TransitionPlan {
+      event:        EventId::E,
+      exit_action:  Some (StateId::A),
+      event_action: Some (EventId::E),
+      entry_action: Some (StateId::B),
+      target:       StateId::B
+    }));
+    assert_eq!(test.preview (&EventId::F.into()),
+      Err (HandleEventException::WrongState));
+    assert_eq!(test.preview (&EventId::G.into()), Ok (TransitionPlan {
+      event:        EventId::G,
+      exit_action:  Some (StateId::A),
+      event_action: None,
+      entry_action: None,
+      target:       StateId::A
+    }));
+    // empty actions are not reported
+    assert_eq!(test.preview (&EventId::H.into()), Ok (TransitionPlan {
+      event:        EventId::H,
+      exit_action:  None,
+      event_action: None,
+      entry_action: None,
+      target:       StateId::A
+    }));
+    assert!(!StateId::B.has_exit_action());
+    assert_eq!(test.as_ref().count, 0);
+    test.handle_event (EventId::E.into()).unwrap();
+    assert_eq!(test.preview (&EventId::F.into()), Ok (TransitionPlan {
+      event:        EventId::F,
+      exit_action:  None,
+      event_action: Some (EventId::F),
+      entry_action: None,
+      target:       StateId::B
+    }));
+  }
+  #[test]
+  fn analysis() {
+    def_machine!{
+      Test () {
+        STATES [
+          state A ()
+          state B ()
+          state C ()
+          state D ()
+          state E ()
+        ]
+        EVENTS [
+          event AB <A> => <B> ()
+          event BA <B> => <A> ()
+          event AC <A> => <C> ()
+          event CC <C> ()
+          event DE <D> => <E> ()
+        ]
+        initial_state: A
+        terminal_state: E
+      }
+    }
+    let report = analysis::analyze::<Test>();
+    assert_eq!(report, analysis::Report {
+      unreachable_states:     vec![StateId::D, StateId::E],
+      dead_end_states:        vec![StateId::C],
+      non_terminating_states: vec![StateId::A, StateId::B, StateId::C],
+      dead_events:            vec![EventId::DE]
+    });
+    assert_eq!(report.to_string(), "unreachable states: [D, E]\n\
+      dead end states: [C]\n\
+      states that can not reach the terminal state: [A, B, C]\n\
+      events that can never be handled: [DE]\n");
+    {
+      def_machine!{
+        Test () {
+          STATES [
+            state A ()
+            state B ()
+          ]
+          EVENTS [
+            event AB <A> => <B> ()
+            event Reset <*> => <A> ()
+          ]
+          initial_state: A
+          terminal_state: B
+        }
+      }
+      analysis::assert_structure::<Test>();
+    }
+  }
+
+  #[test]
+  fn validate() {
+    trybuild::TestCases::new().compile_fail ("tests/compile-fail/*.rs");
+  }
+
+  #[test]
+  fn large_machine() {
+    // the name checks must not recurse once per state or event
+    def_machine!{
+      Test () {
+        STATES [
+          state S0 () state S1 () state S2 () state S3 () state S4 ()
+          state S5 () state S6 () state S7 () state S8 () state S9 ()
+          state S10 () state S11 () state S12 () state S13 () state S14 ()
+          state S15 () state S16 () state S17 () state S18 () state S19 ()
+          state S20 () state S21 () state S22 () state S23 () state S24 ()
+          state S25 () state S26 () state S27 () state S28 () state S29 ()
+          state S30 () state S31 () state S32 () state S33 () state S34 ()
+          state S35 () state S36 () state S37 () state S38 () state S39 ()
+          state S40 () state S41 () state S42 () state S43 () state S44 ()
+          state S45 () state S46 () state S47 () state S48 () state S49 ()
+          state S50 () state S51 () state S52 () state S53 () state S54 ()
+          state S55 () state S56 () state S57 () state S58 () state S59 ()
+          state S60 () state S61 () state S62 () state S63 () state S64 ()
+          state S65 () state S66 () state S67 () state S68 () state S69 ()
+          state S70 () state S71 () state S72 () state S73 () state S74 ()
+          state S75 () state S76 () state S77 () state S78 () state S79 ()
+          state S80 () state S81 () state S82 () state S83 () state S84 ()
+          state S85 () state S86 () state S87 () state S88 () state S89 ()
+          state S90 () state S91 () state S92 () state S93 () state S94 ()
+          state S95 () state S96 () state S97 () state S98 () state S99 ()
+          state S100 () state S101 () state S102 () state S103 () state S104 ()
+          state S105 () state S106 () state S107 () state S108 () state S109 ()
+          state S110 () state S111 () state S112 () state S113 () state S114 ()
+          state S115 () state S116 () state S117 () state S118 () state S119 ()
+          state S120 () state S121 () state S122 () state S123 () state S124 ()
+          state S125 () state S126 () state S127 () state S128 () state S129 ()
+        ]
+        EVENTS [
+          event E0 <S0> => <S1> () event E1 <S1> => <S2> ()
+          event E2 <S2> => <S3> () event E3 <S3> => <S4> ()
+          event E4 <S4> => <S5> () event E5 <S5> => <S6> ()
+          event E6 <S6> => <S7> () event E7 <S7> => <S8> ()
+          event E8 <S8> => <S9> () event E9 <S9> => <S10> ()
+          event E10 <S10> => <S11> () event E11 <S11> => <S12> ()
+          event E12 <S12> => <S13> () event E13 <S13> => <S14> ()
+          event E14 <S14> => <S15> () event E15 <S15> => <S16> ()
+          event E16 <S16> => <S17> () event E17 <S17> => <S18> ()
+          event E18 <S18> => <S19> () event E19 <S19> => <S20> ()
+          event E20 <S20> => <S21> () event E21 <S21> => <S22> ()
+          event E22 <S22> => <S23> () event E23 <S23> => <S24> ()
+          event E24 <S24> => <S25> () event E25 <S25> => <S26> ()
+          event E26 <S26> => <S27> () event E27 <S27> => <S28> ()
+          event E28 <S28> => <S29> () event E29 <S29> => <S30> ()
+          event E30 <S30> => <S31> () event E31 <S31> => <S32> ()
+          event E32 <S32> => <S33> () event E33 <S33> => <S34> ()
+          event E34 <S34> => <S35> () event E35 <S35> => <S36> ()
+          event E36 <S36> => <S37> () event E37 <S37> => <S38> ()
+          event E38 <S38> => <S39> () event E39 <S39> => <S40> ()
+          event E40 <S40> => <S41> () event E41 <S41> => <S42> ()
+          event E42 <S42> => <S43> () event E43 <S43> => <S44> ()
+          event E44 <S44> => <S45> () event E45 <S45> => <S46> ()
+          event E46 <S46> => <S47> () event E47 <S47> => <S48> ()
+          event E48 <S48> => <S49> () event E49 <S49> => <S50> ()
+          event E50 <S50> => <S51> () event E51 <S51> => <S52> ()
+          event E52 <S52> => <S53> () event E53 <S53> => <S54> ()
+          event E54 <S54> => <S55> () event E55 <S55> => <S56> ()
+          event E56 <S56> => <S57> () event E57 <S57> => <S58> ()
+          event E58 <S58> => <S59> () event E59 <S59> => <S60> ()
+          event E60 <S60> => <S61> () event E61 <S61> => <S62> ()
+          event E62 <S62> => <S63> () event E63 <S63> => <S64> ()
+          event E64 <S64> => <S65> () event E65 <S65> => <S66> ()
+          event E66 <S66> => <S67> () event E67 <S67> => <S68> ()
+          event E68 <S68> => <S69> () event E69 <S69> => <S70> ()
+          event E70 <S70> => <S71> () event E71 <S71> => <S72> ()
+          event E72 <S72> => <S73> () event E73 <S73> => <S74> ()
+          event E74 <S74> => <S75> () event E75 <S75> => <S76> ()
+          event E76 <S76> => <S77> () event E77 <S77> => <S78> ()
+          event E78 <S78> => <S79> () event E79 <S79> => <S80> ()
+          event E80 <S80> => <S81> () event E81 <S81> => <S82> ()
+          event E82 <S82> => <S83> () event E83 <S83> => <S84> ()
+          event E84 <S84> => <S85> () event E85 <S85> => <S86> ()
+          event E86 <S86> => <S87> () event E87 <S87> => <S88> ()
+          event E88 <S88> => <S89> () event E89 <S89> => <S90> ()
+          event E90 <S90> => <S91> () event E91 <S91> => <S92> ()
+          event E92 <S92> => <S93> () event E93 <S93> => <S94> ()
+          event E94 <S94> => <S95> () event E95 <S95> => <S96> ()
+          event E96 <S96> => <S97> () event E97 <S97> => <S98> ()
+          event E98 <S98> => <S99> () event E99 <S99> => <S100> ()
+          event E100 <S100> => <S101> () event E101 <S101> => <S102> ()
+          event E102 <S102> => <S103> () event E103 <S103> => <S104> ()
+          event E104 <S104> => <S105> () event E105 <S105> => <S106> ()
+          event E106 <S106> => <S107> () event E107 <S107> => <S108> ()
+          event E108 <S108> => <S109> () event E109 <S109> => <S110> ()
+          event E110 <S110> => <S111> () event E111 <S111> => <S112> ()
+          event E112 <S112> => <S113> () event E113 <S113> => <S114> ()
+          event E114 <S114> => <S115> () event E115 <S115> => <S116> ()
+          event E116 <S116> => <S117> () event E117 <S117> => <S118> ()
+          event E118 <S118> => <S119> () event E119 <S119> => <S120> ()
+          event E120 <S120> => <S121> () event E121 <S121> => <S122> ()
+          event E122 <S122> => <S123> () event E123 <S123> => <S124> ()
+          event E124 <S124> => <S125> () event E125 <S125> => <S126> ()
+          event E126 <S126> => <S127> () event E127 <S127> => <S128> ()
+          event E128 <S128> => <S129> () event E129 <S129> => <S0> ()
+        ]
+        initial_state: S0
+      }
+    }
+    assert_eq!(Test::states().len(), 130);
+    let mut test = Test::initial();
+    for _ in 0..130 {
+      let event = test.enabled_events().next().unwrap();
+      test.handle_event (event.into()).unwrap();
+    }
+    assert_eq!(*test.state().id(), StateId::S0);
+  }
+
+  #[test]
+  fn paths() {
+    def_machine!{
+      Test () {
+        STATES [
+          state A ()
+          state B ()
+          state C ()
+          state D ()
+        ]
+        EVENTS [
+          event AB <A> => <B> ()
+          event BB <B> ()
+          event BC <B> => <C> ()
+          event ToA <*> => <A> ()
+          event AC <A> => <C> (n : u8 = 1)
+        ]
+        initial_state: A
+      }
+    }
+    assert_eq!(paths::shortest::<Test> (&StateId::B, &StateId::C),
+      Some (vec![EventId::BC]));
+    assert_eq!(paths::shortest::<Test> (&StateId::C, &StateId::B),
+      Some (vec![EventId::ToA, EventId::AB]));
+    assert_eq!(paths::shortest::<Test> (&StateId::B, &StateId::B),
+      Some (vec![]));
+    assert_eq!(paths::shortest::<Test> (&StateId::A, &StateId::D), None);
+    assert_eq!(paths::from_initial::<Test>(), vec![
+      (StateId::A, vec![]),
+      (StateId::B, vec![EventId::AB]),
+      (StateId::C, vec![EventId::AC])
+    ]);
+    for (state, events) in Event::paths() {
+      let mut test = Test::initial();
+      for event in events {
+        test.handle_event (event).unwrap();
+      }
+      assert_eq!(test.state_id(), state);
+    }
+    let path = Event::path (&StateId::A, &StateId::C).unwrap();
+    assert_eq!(path.len(), 1);
+    match path[0].params() {
+      EventParams::AC { n } => assert_eq!(*n, 1),
+      _ => unreachable!()
+    }
+  }
+
+  #[test]
+  fn transition_tour() {
+    def_machine_debug!{
+      Test () @ test {
+        STATES [
+          state A ()
+          state B (count : u8)
+          state C ()
+          state D ()
+        ]
+        EVENTS [
+          event AB <A> => <B> ()
+          event BB <B> () { count } => { *count += 1; }
+          event BC <B> => <C> ()
+          event CA <C> => <A> ()
+          event ToB <*> => <B> ()
+          event DA <D> => <A> ()
+        ]
+        initial_state: A
+        terminal_state: C {
+          terminate_failure: { panic!("not in terminal state") }
+        }
+      }
+    }
+    // the universal event is taken from each state and the tour repeats BC
+    // twice, the least needed to return to B and end in the terminal state
+    assert_eq!(paths::tour::<Test>(), vec![
+      (EventId::AB,  StateId::B),
+      (EventId::BB,  StateId::B),
+      (EventId::BC,  StateId::C),
+      (EventId::CA,  StateId::A),
+      (EventId::ToB, StateId::B),
+      (EventId::ToB, StateId::B),
+      (EventId::BC,  StateId::C),
+      (EventId::ToB, StateId::B),
+      (EventId::BC,  StateId::C)
+    ]);
+    Test::assert_transition_tour();
+  }
+
+  #[test]
+  fn random_walk() {
+    def_machine_debug!{
+      Test (count : u8) @ test {
+        STATES [
+          state A ()
+          state B ()
+        ]
+        EVENTS [
+          event AB <A> => <B> () {} => { *count += 1; }
+          event BA <B> => <A> ()
+          event BB <B> ()
+        ]
+        initial_state: A
+        terminal_state: A {
+          terminate_failure: { panic!("not in terminal state") }
+        }
+      }
+    }
+    for seed in 0..10 {
+      let invariant = |test : &Test| if test.extended_state().count <= 50 {
+        Ok (())
+      } else {
+        Err ("count too large".to_string())
+      };
+      let mut test = Test::assert_random_walk (seed, 50, invariant);
+      let mut walked = Test::initial();
+      walked.random_walk (&mut walk::SplitMix64::new (seed), 50,
+        |id, _| id.into(), invariant).unwrap();
+      assert_eq!(test.state_id(), walked.state_id());
+      for machine in [&mut test, &mut walked] {
+        if machine.state_id() == StateId::B {
+          machine.handle_event (EventId::BA.into()).unwrap();
+        }
+      }
+    }
+    // the failed machine is leaked instead of panicking on termination
+    let panic = std::panic::catch_unwind (|| Test::assert_random_walk (7, 100,
+      |test| if test.extended_state().count < 3 {
+        Ok (())
+      } else {
+        Err ("count is 3".to_string())
+      })).unwrap_err();
+    assert!(panic.downcast_ref::<String>().unwrap()
+      .contains ("invariant violated: count is 3"));
+    let mut test = Test::initial();
+    let mut rng = walk::SplitMix64::new (7);
+    let failure = test.random_walk (&mut rng, 100, |id, _| id.into(),
+      |test| if test.extended_state().count < 3 {
+        Ok (())
+      } else {
+        Err ("count is 3".to_string())
+      }).unwrap_err();
+    assert_eq!(failure.seed, Some (7));
+    assert_eq!(failure.message, "invariant violated: count is 3");
+    assert_eq!(failure.trace.iter().filter (|id| **id == EventId::AB).count(), 3);
+    assert_eq!(failure.trace.last(), Some (&EventId::AB));
+    assert_eq!(test.state_id(), StateId::B);
+    test.handle_event (EventId::BA.into()).unwrap();
+  }
+
+  #[test]
+  fn coverage() {
+    def_machine_debug!{
+      Test (count : u64) {
+        STATES [
+          state A () {
+            entry { *count += 1; }
+            exit  { *count += 1; }
+          }
+          state B ()
+        ]
+        EVENTS [
+          event AB <A> => <B> ()
+          event BB <B> ()
+          event Reset <*> => <A> ()
+        ]
+        initial_state: A
+      }
+    }
+    let mut test = Test::initial();
+    assert!(test.coverage().is_none());
+    test.enable_coverage();
+    test.handle_event (EventId::AB.into()).unwrap();
+    test.handle_event (EventId::AB.into()).unwrap_err();
+    test.handle_event (EventId::Reset.into()).unwrap();
+    let report = test.coverage().unwrap();
+    assert!(!report.is_complete());
+    assert_eq!(report.uncovered_transitions().map (ToString::to_string)
+      .collect::<Vec <_>>(), ["BB <B>", "Reset <*> => <A> from <A>"]);
+    // the initial entry action ran before tracking started
+    assert_eq!(report.to_string(),
+      "Test coverage: 2/4 transitions, 1/1 entry actions, 1/1 exit actions\n  \
+        uncovered transition: BB <B>\n  \
+        uncovered transition: Reset <*> => <A> from <A>\n");
+    let mut fresh = Test::initial();
+    fresh.enable_coverage();
+    assert_eq!(fresh.coverage().unwrap().to_string(),
+      "Test coverage: 0/4 transitions, 0/1 entry actions, 0/1 exit actions\n  \
+        uncovered transition: AB <A> => <B>\n  \
+        uncovered transition: BB <B>\n  \
+        uncovered transition: Reset <*> => <A> from <A>\n  \
+        uncovered transition: Reset <*> => <A> from <B>\n  \
+        uncovered entry action: A\n  \
+        uncovered exit action: A\n");
+    let dotfile = Test::dotfile_coverage (&report);
+    assert!(dotfile.contains ("AB</FONT>>, color=green, fontcolor=green]"));
+    assert!(dotfile.contains ("BB</FONT>>, color=red, fontcolor=red]"));
+    assert!(dotfile.contains ("Reset</FONT>>, color=red, fontcolor=red]"));
+    let mut other = Test::initial();
+    other.enable_coverage();
+    other.handle_event (EventId::Reset.into()).unwrap();
+    let mut merged = report;
+    merged.merge (&other.coverage().unwrap());
+    assert_eq!(merged.uncovered_transitions().map (ToString::to_string)
+      .collect::<Vec <_>>(), ["BB <B>"]);
+    let dotfile = Test::dotfile_coverage (&merged);
+    assert!(dotfile.contains ("Reset</FONT>>, color=green, fontcolor=green]"));
+  }
+
+  #[test]
+  fn scenario() {
+    def_machine_debug!{
+      Test () {
+        STATES [
+          state A ()
+          state B ()
+        ]
+        EVENTS [
+          event AB <A> => <B> ()
+          event BB <B> (n : u8)
+          event BA <B> => <A> ()
+        ]
+        initial_state: A
+      }
+    }
+    let mut test = Test::initial();
+    assert_machine_scenario!{ test,
+      AB => B,
+      AB => Err (WrongState),
+      (EventParams::BB { n: 1 }.into()) => B,
+      BA => A
+    }
+    let result = std::panic::catch_unwind (|| {
+      let mut test = Test::initial();
+      assert_machine_scenario!{ test,
+        AB => B,
+        BB => A
+      }
+    });
+    let message = result.unwrap_err().downcast::<String>().unwrap();
+    assert_eq!(*message,
+      "scenario step 2: event BB in state B: expected state A but got state B");
+    // long scenarios do not run into the macro recursion limit
+    let mut test = Test::initial();
+    assert_machine_scenario!{ test,
+      AB => B, BA => A, AB => B, BA => A, AB => B, BA => A, AB => B, BA => A,
+      AB => B, BA => A, AB => B, BA => A, AB => B, BA => A, AB => B, BA => A,
+      AB => B, BA => A, AB => B, BA => A, AB => B, BA => A, AB => B, BA => A,
+      AB => B, BA => A, AB => B, BA => A, AB => B, BA => A, AB => B, BA => A,
+      AB => B, BA => A, AB => B, BA => A, AB => B, BA => A, AB => B, BA => A,
+      AB => B, BA => A, AB => B, BA => A, AB => B, BA => A, AB => B, BA => A,
+      AB => B, BA => A, AB => B, BA => A, AB => B, BA => A, AB => B, BA => A,
+      AB => B, BA => A, AB => B, BA => A, AB => B, BA => A, AB => B, BA => A,
+      AB => B, BA => A, AB => B, BA => A, AB => B, BA => A, AB => B, BA => A,
+      AB => B, BA => A, AB => B, BA => A, AB => B, BA => A, AB => B, BA => A,
+      AB => B, BA => A, AB => B, BA => A, AB => B, BA => A, AB => B, BA => A,
+      AB => B, BA => A, AB => B, BA => A, AB => B, BA => A, AB => B, BA => A,
+      AB => B, BA => A, AB => B, BA => A, AB => B, BA => A, AB => B, BA => A,
+      AB => B, BA => A, AB => B, BA => A, AB => B, BA => A, AB => B, BA => A,
+      AB => B, BA => A, AB => B, BA => A, AB => B, BA => A, AB => B, BA => A,
+      AB => B, BA => A, AB => B, BA => A, AB => B, BA => A, AB => B, BA => A,
+      AB => B, BA => A, AB => B, BA => A, AB => B, BA => A, AB => B, BA => A,
+      AB => B, BA => A, AB => B, BA => A, AB => B, BA => A, AB => B, BA => A,
+      AB => B, BA => A, AB => B, BA => A, AB => B, BA => A, AB => B, BA => A,
+      AB => B, BA => A, AB => B, BA => A, AB => B, BA => A, AB => B, BA => A
+    }
+  }
+
+  #[test]
+  fn golden() {
+    def_machine_debug!{
+      Door (open_count : u64 = 1 + 1) @ door {
+        STATES [
+          state Closed (knock_count : u64, locked : bool = true) {
+            exit { *knock_count = 0; }
+          }
+          state Opened ()
+        ]
+        EVENTS [
+          event Knock <Closed> () { knock_count } => { *knock_count += 1; }
+          event Open  <Closed> => <Opened> () {} => { *open_count += 1; }
+          event Close <Opened> => <Closed> ()
+          event Reset <*> => <Closed> ()
+        ]
+        initial_state:  Closed {
+          initial_action: { door.as_mut().open_count = 0; }
+        }
+        terminal_state: Closed {
+          terminate_success: { }
+          terminate_failure: { panic!("door was left: {:?}", door.state()) }
+        }
+      }
+    }
+    golden::assert_dotfiles::<Door> (std::path::Path::new (
+      concat!(env!("CARGO_MANIFEST_DIR"), "/tests/golden")));
+    assert_eq!(golden::diff ("a\nb\nc\n", "a\nc\nd\n"),
+      "-   2: b\n+   3: d\n");
+  }
+
+  #[test]
+  fn mermaid() {
+    def_machine!{
+      Test () {
+        STATES [
+          state A ()
+          state B ()
+          state C ()
+        ]
+        EVENTS [
+          event AB <A> => <B> ()
+          event BB <B> ()
+          event Reset <*> => <A> ()
+        ]
+        initial_state:  A
+        terminal_state: B
+      }
+    }
+    assert_eq!(Test::mermaid(), "---\ntitle: Test\n---\nstateDiagram-v2\n  \
+      state A\n  \
+      state B\n  \
+      state C\n  \
+      [*] --> A\n  \
+      A --> B : AB\n  \
+      B --> B : BB\n  \
+      A --> A : Reset\n  \
+      B --> A : Reset\n  \
+      C --> A : Reset\n  \
+      B --> [*]\n");
+  }
+
+  #[test]
+  fn plantuml() {
+    def_machine!{
+      Test (count : u8, name : String) {
+        STATES [
+          state A (x : u8, y : Option <bool>)
+          state B ()
+        ]
+        EVENTS [
+          event AB <A> => <B> ()
+          event Reset <*> => <A> ()
+        ]
+        initial_state:  A
+        terminal_state: B
+      }
+    }
+    assert_eq!(Test::plantuml(), "@startuml\ntitle Test\n\
+      note as ExtendedState\n  count : u8\n  name : String\nend note\n\
+      state A\nA : x : u8\nA : y : Option <bool>\n\
+      state B\n\
+      [*] --> A\n\
+      A --> B : AB\n\
+      A --> A : Reset\n\
+      B --> A : Reset\n\
+      B --> [*]\n\
+      @enduml\n");
+  }
+
+  #[test]
+  fn scxml() {
+    def_machine!{
+      Test (count : u8) {
+        STATES [
+          state A (n : u8)
+          state B ()
+          state C ()
+        ]
+        EVENTS [
+          event AA <A> () { n } => { *n += 1; }
+          event AB <A> => <B> () {} => { *count = 1 << 2; }
+          event BC <B> => <C> ()
+        ]
+        initial_state:  A
+        terminal_state: C
+      }
+    }
+    assert_eq!(Test::scxml(), r#"<?xml version="1.0" encoding="UTF-8"?>
+<scxml xmlns="http://www.w3.org/2005/07/scxml" version="1.0" name="Test" initial="A">
+  <state id="A">
+    <transition event="AA">
+      <script>{ *n += 1; }</script>
+    </transition>
+    <transition event="AB" target="B">
+      <script>{ *count = 1 &lt;&lt; 2; }</script>
+    </transition>
+  </state>
+  <state id="B">
+    <transition event="BC" target="C"/>
+  </state>
+  <final id="C"/>
+</scxml>
+"#);
+    {
+      def_machine!{
+        Test () {
+          STATES [
+            state Test ()
+            state B ()
+            state C ()
+          ]
+          EVENTS [
+            event TestB <Test> => <B> ()
+            event BC <B> => <C> ()
+            event Reset <*> => <Test> ()
+          ]
+          initial_state:  Test
+          terminal_state: C
+        }
+      }
+      assert_eq!(Test::scxml(), r#"<?xml version="1.0" encoding="UTF-8"?>
+<scxml xmlns="http://www.w3.org/2005/07/scxml" version="1.0" name="Test" initial="Test-universal">
+  <state id="Test-universal" initial="Test">
+    <state id="Test">
+      <transition event="TestB" target="B"/>
+    </state>
+    <state id="B">
+      <transition event="BC" target="C"/>
+    </state>
+    <transition event="Reset" target="Test"/>
+  </state>
+  <final id="C"/>
+</scxml>
+"#);
+    }
+  }
+
+  #[test]
+  fn dotfile_params() {
+    def_machine!{
+      Test () {
+        STATES [
+          state A ()
+        ]
+        EVENTS [
+          event X <A> (x : u8 = 2, y : bool) {} => { }
+          event Y <A> (z : u8) {} => { let _ = z; }
+        ]
+        initial_state: A
+      }
+    }
+    let edges = |dotfile : String| dotfile.lines()
+      .filter (|line| line.starts_with ("    \"A\""))
+      .map (str::to_string).collect::<Vec <_>>();
+    assert_eq!(edges (Test::dotfile()), [
+      "    \"A\" -> \"A\" [label=<<FONT FACE=\"Sans Italic\">X</FONT>\
+        <FONT FACE=\"Mono\">&#40;x : u8, y : bool&#41;</FONT>>]",
+      "    \"A\" -> \"A\" [label=<<FONT FACE=\"Sans Italic\">Y</FONT>\
+        <FONT FACE=\"Mono\">&#40;z : u8&#41;<BR/>\
+        &#123; let _ &#61; z; &#125;</FONT>>]"
+    ]);
+    assert!(edges (Test::dotfile_with (
+      &DotfileOptions::new().defaults (Defaults::Expression)
+    ))[0].contains ("&#40;x : u8 &#61; 2, y : bool&#41;"));
+    assert_eq!(
+      edges (Test::dotfile_with (&DotfileOptions::new().params (false))), [
+      "    \"A\" -> \"A\" [label=<<FONT FACE=\"Sans Italic\">X</FONT>>]",
+      "    \"A\" -> \"A\" [label=<<FONT FACE=\"Sans Italic\">Y</FONT>\
+        <FONT FACE=\"Mono\"><BR/>&#123; let _ &#61; z; &#125;</FONT>>]"
+    ]);
+    #[expect(deprecated)]
+    let hidden = Test::dotfile_hide_params();
+    assert_eq!(hidden,
+      Test::dotfile_with (&DotfileOptions::new().params (false)));
+  }
+
+  #[test]
+  fn dotfile_options() {
+    def_machine!{
+      Test () {
+        STATES [
+          state A ()
+          state B ()
+        ]
+        EVENTS [
+          event AB <A> => <B> ()
+          event Reset <*> => <A> ()
+        ]
+        initial_state: A
+      }
+    }
+    let dotfile = Test::dotfile();
+    assert!(dotfile.contains ("    \"*\" -> \"A\" [label=<<FONT FACE=\"Sans Italic\">\
+      Reset</FONT>>]\n"));
+    assert!(dotfile.contains ("    B -> \"*\" [style=dashed, color=gray]\n"));
+    // each universal source edge is terminated by a newline
+    assert!(dotfile.lines().all (|line| line.matches ("->").count() <= 1));
+    let dotfile = Test::dotfile_with (&DotfileOptions::new()
+      .rankdir ("TB\" color=\"red")
+      .font ("My \"Sans\"")
+      .node_color ("#ff0000"));
+    assert!(dotfile.contains ("rankdir=\"TB\\\" color=\\\"red\"\n"));
+    assert!(dotfile.contains ("node [shape=record, style=rounded, \
+      fontname=\"My \\\"Sans\\\" Bold\", color=\"#ff0000\", \
+      fontcolor=\"#ff0000\"]\n"));
+    assert!(dotfile.contains ("<FONT FACE=\"My &#34;Sans&#34; Italic\">AB"));
+    {
+      def_machine!{
+        Test (count : u8) {
+          STATES [
+            state A () {
+              entry { *count += 1; }
+            }
+          ]
+          EVENTS [ ]
+          initial_state: A
+        }
+      }
+      let entry = "entry / &#123; *count &#43;&#61; 1; &#125;";
+      assert!(Test::dotfile().contains (entry));
+      assert!(!Test::dotfile_with (&DotfileOptions::new().actions (false))
+        .contains (entry));
+      assert!(!Test::dotfile_with (&DotfileOptions::new().entry_exit (false))
+        .contains (entry));
+    }
+    let dotfile = Test::dotfile_with (&DotfileOptions::new()
+      .expand_universal (true)
+      .rankdir ("TB")
+      .font ("Serif")
+      .edge_color ("blue"));
+    assert!(dotfile.contains ("rankdir=\"TB\"\n"));
+    assert!(dotfile.contains (
+      "edge [fontname=\"Serif\", color=\"blue\", fontcolor=\"blue\"]\n"));
+    assert!(!dotfile.contains ("\"*\""));
+    for source in ["A", "B"] {
+      assert!(dotfile.contains (format!(
+        "    \"{source}\" -> \"A\" [label=<<FONT FACE=\"Serif Italic\">\
+          Reset</FONT>>]\n").as_str()));
+    }
+  }
+
+  #[test]
+  fn dotfile_instance() {
+    def_machine_debug!{
+      Test (count : u8 = 1) {
+        STATES [
+          state A (n : u8 = 3, flag : bool)
+          state B ()
+        ]
+        EVENTS [
+          event AA <A> () { n } => { *n += 1; }
+          event AB <A> => <B> ()
+          event BA <B> => <A> ()
+        ]
+        initial_state: A
+      }
+    }
+    let mut test = Test::initial();
+    test.handle_event (EventId::AA.into()).unwrap();
+    let dotfile = test.dotfile_instance();
+    assert!(dotfile.contains ("count : u8 &#61; 1"));
+    assert!(dotfile.contains ("n    : u8   &#61; 4,"));
+    assert!(dotfile.contains ("flag : bool &#61; false"));
+    assert!(dotfile.contains (
+      "</FONT>>, style=\"rounded,filled\", fillcolor=lightblue]\n"));
+    assert!(dotfile.contains ("    B [label=<<B>B</B>>]\n"));
+    let edges : Vec <_> = dotfile.lines().filter (|line| line.contains ("->"))
+      .filter (|line| line.ends_with ("penwidth=2]")).collect();
+    assert_eq!(edges.len(), 2);
+    assert!(edges[0].starts_with ("    \"A\" -> \"A\""));
+    assert!(edges[1].starts_with ("    \"A\" -> \"B\""));
+    {
+      // without `Debug` the values are left out
+      def_machine!{
+        Test (count : u8 = 1) {
+          STATES [
+            state A (n : u8 = 3)
+            state B ()
+          ]
+          EVENTS [
+            event AB <A> => <B> ()
+          ]
+          initial_state: A
+        }
+      }
+      let dotfile = Test::initial().dotfile_instance();
+      assert!(dotfile.contains ("count : u8<BR ALIGN=\"LEFT\"/>"));
+      assert!(dotfile.contains ("n : u8<BR ALIGN=\"LEFT\"/>"));
+      assert!(!dotfile.contains ("&#61;"));
+      assert!(dotfile.contains (
+        "</FONT>>, style=\"rounded,filled\", fillcolor=lightblue]\n"));
+      assert!(dotfile.contains ("    \"A\" -> \"B\" [label=<<FONT FACE=\"Sans \
+        Italic\">AB</FONT>>, color=blue, fontcolor=blue, penwidth=2]\n"));
+    }
+  }
+
+  #[test]
+  fn machine_json() {
+    def_machine!{
+      Test (count : u8 = 1) {
+        STATES [
+          state A (n : u8) {
+            entry { *count += 1; }
+          }
+          state B ()
+        ]
+        EVENTS [
+          event AA <A> (x : u8 = 2) { n } => { *n += x; }
+          event AB <A> => <B> (y : u16)
+          event Reset <*> => <A> ()
+        ]
+        initial_state: A
+      }
+    }
+    assert_eq!(Test::event_param_names(), vec![vec!["x"], vec!["y"], vec![]]);
+    assert_eq!(Test::event_param_types(), vec![vec!["u8"], vec!["u16"], vec![]]);
+    assert_eq!(Test::event_param_defaults(), vec![vec!["2"], vec![""], vec![]]);
+    assert_eq!(Test::state_entry_actions(), vec!["{ *count += 1; }", ""]);
+    assert_eq!(Test::state_exit_actions(), vec!["", ""]);
+    assert_eq!(Test::machine_json(), r#"{
+  "schema_version": 1,
+  "name": "Test",
+  "type_vars": [],
+  "self_reference": null,
+  "extended_state": [
+    {
+      "name": "count",
+      "type": "u8",
+      "default": "1"
+    }
+  ],
+  "states": [
+    {
+      "name": "A",
+      "data": [
+        {
+          "name": "n",
+          "type": "u8",
+          "default": "u8::default()"
+        }
+      ],
+      "entry_action": "{ *count += 1; }",
+      "exit_action": null
+    },
+    {
+      "name": "B",
+      "data": [],
+      "entry_action": null,
+      "exit_action": null
+    }
+  ],
+  "initial_state": "A",
+  "initial_action": null,
+  "terminal_state": null,
+  "terminate_success_action": null,
+  "terminate_failure_action": null,
+  "events": [
+    {
+      "name": "AA",
+      "source": "A",
+      "target": null,
+      "params": [
+        {
+          "name": "x",
+          "type": "u8",
+          "default": "2"
+        }
+      ],
+      "action": "{ *n += x; }"
+    },
+    {
+      "name": "AB",
+      "source": "A",
+      "target": "B",
+      "params": [
+        {
+          "name": "y",
+          "type": "u16",
+          "default": null
+        }
+      ],
+      "action": null
+    },
+    {
+      "name": "Reset",
+      "source": null,
+      "target": "A",
+      "params": [],
+      "action": null
+    }
+  ]
+}
+"#);
+  }
+
+  #[cfg(feature = "scxml")]
+  #[test]
+  fn scxml_import() {
+    def_machine!{
+      Test (count : u8) {
+        STATES [
+          state A ()
+          state B ()
+          state C ()
+        ]
+        EVENTS [
+          event AA <A> () {} => { *count += 1; }
+          event AB <A> => <B> ()
+          event BC <B> => <C> ()
+          event Reset <*> => <A> ()
+        ]
+        initial_state:  A
+        terminal_state: C
+      }
+    }
+    assert_eq!(scxml::to_def_machine (&Test::scxml()).unwrap(),
+"macro_machines::def_machine!{
+  Test () {
+    STATES [
+      state A ()
+      state B ()
+      state C ()
+    ]
+    EVENTS [
+      event AA <A> () {} => {
+        todo!()
+      }
+      event AB <A> => <B> () {} => {
+        todo!()
+      }
+      event BC <B> => <C> () {} => {
+        todo!()
+      }
+      event ResetFromA <A> => <A> () {} => {
+        todo!()
+      }
+      event ResetFromB <B> => <A> () {} => {
+        todo!()
+      }
+    ]
+    initial_state: A
+    terminal_state: C
+  }
+}
+");
+    assert_eq!(scxml::to_def_machine (r#"<scxml initial="s-1">
+  <state id="s-1"><transition event="go" target="s-2"/></state>
+  <state id="s-2"><transition event="go" target="s-1"/></state>
+</scxml>"#).unwrap().lines()
+      .filter (|line| line.trim_start().starts_with ("event"))
+      .collect::<Vec <_>>(), [
+        "      event GoFromS1 <S1> => <S2> () {} => {",
+        "      event GoFromS2 <S2> => <S1> () {} => {"
+      ]);
+    let machine = scxml::to_def_machine (r#"<scxml>
+  <state>
+    <transition event="go" cond="ready" target="b"/>
+    <transition event="go" target="State1"/>
+    <transition event="stop"/>
+  </state>
+  <state id="b"/>
+  <state id="State1"/>
+  <state/>
+</scxml>"#).unwrap();
+    assert_eq!(machine.lines()
+      .filter (|line| line.contains ("state"))
+      .collect::<Vec <_>>(), [
+        "      state State2 ()",
+        "      state B ()",
+        "      state State1 ()",
+        "      state State3 ()",
+        "    initial_state: State2"
+      ]);
+    assert_eq!(machine.lines()
+      .filter (|line| line.trim_start().starts_with ("event"))
+      .collect::<Vec <_>>(), [
+        "      event Go <State2> => <B> () {} => {",
+        "      event Go2 <State2> => <State1> () {} => {",
+        "      event Stop <State2> () {} => {"
+      ]);
+    // compound targets and the default initial state resolve to the initial
+    // atomic state, and colliding identifiers are numbered
+    let machine = scxml::to_def_machine (r#"<scxml>
+  <state id="outer">
+    <state id="inner" initial="s-1">
+      <state id="S1"><transition event="go" target="inner"/></state>
+      <state id="s-1"><transition event="go" target="outer"/></state>
+    </state>
+  </state>
+  <state id="s1"><transition event="go" target="outer"/></state>
+</scxml>"#).unwrap();
+    assert_eq!(machine.lines()
+      .filter (|line| line.contains ("state"))
+      .collect::<Vec <_>>(), [
+        "      state S1 ()",
+        "      state S12 ()",
+        "      state S13 ()",
+        "    initial_state: S12"
+      ]);
+    assert_eq!(machine.lines()
+      .filter (|line| line.trim_start().starts_with ("event"))
+      .collect::<Vec <_>>(), [
+        "      event GoFromS1 <S1> => <S12> () {} => {",
+        "      event GoFromS12 <S12> => <S12> () {} => {",
+        "      event GoFromS13 <S13> => <S12> () {} => {"
+      ]);
+    assert_eq!(
+      scxml::to_def_machine (r#"<scxml><parallel id="p"/></scxml>"#),
+      Err (scxml::ImportError (
+        "parallel state p is not supported".to_string())));
+  }
+
+  #[cfg(feature = "arbitrary")]
+  #[test]
+  fn arbitrary() {
+    use arbitrary::{Arbitrary, Unstructured};
+    def_machine!{
+      Test () {
+        STATES [
+          state A ()
+        ]
+        EVENTS [
+          event X <A> (x : u8)
+          event Y <A> (y : bool, z : u16)
+        ]
+        fuzz: arbitrary
+        initial_state: A
+      }
+    }
+    let data = [1, 7, 0, 1, 2, 3, 4, 5, 6, 7, 8];
+    let mut u = Unstructured::new (&data);
+    let events = Vec::<Event>::arbitrary (&mut u).unwrap();
+    let mut test = Test::initial();
+    for event in events {
+      test.handle_event (event).unwrap();
+    }
+    let mut u = Unstructured::new (&[1, 1, 0x34, 0x12]);
+    match Event::arbitrary (&mut u).unwrap().params {
+      EventParams::Y { y, z } => assert_eq!((y, z), (true, 0x1234)),
+      _ => unreachable!()
+    }
+  }
+
+  #[cfg(feature = "proptest")]
+  #[test]
+  fn proptest() {
+    use proptest::test_runner::TestRunner;
+    def_machine_debug!{
+      Test () {
+        STATES [
+          state A ()
+          state B ()
+        ]
+        EVENTS [
+          event X <A> => <B> (x : u8)
+          event Y <*> => <A> (y : bool, z : u16)
+        ]
+        fuzz: proptest
+        initial_state: A
+      }
+    }
+    let mut runner = TestRunner::deterministic();
+    runner.run (&Event::sequence_strategy (0..10), |events| {
+      let mut test = Test::initial();
+      for event in events {
+        let target = match event.id() {
+          EventId::X => StateId::B,
+          EventId::Y => StateId::A
+        };
+        if test.can_handle (event.id()) {
+          test.handle_event (event).unwrap();
+          assert_eq!(test.state_id(), target);
+        }
+      }
+      Ok (())
+    }).unwrap();
+    runner.run (&EventId::strategy(), |id| {
+      assert!(EventId::ALL.contains (&id));
+      Ok (())
+    }).unwrap();
+  }
 }
@@ -34,6 +34,15 @@ macro_rules! def_machine {
         $($ext_name:ident : $ext_type:ty $(= $ext_default:expr)*),*
       ]
       $(self_reference: $self_reference:ident)*
+      $(logging: {
+        $(target: $log_target:literal)?
+        $(initialize: $log_initialize:ident)?
+        $(accepted: $log_accepted:ident)?
+        $(rejected: $log_rejected:ident)?
+        $(terminate_success: $log_terminate_success:ident)?
+        $(terminate_failure: $log_terminate_failure:ident)?
+      })?
+      $(fuzz: $($fuzz:ident),+)?
       initial_state: $initial:ident $({
         $(initial_action: $initial_action:block)*
       })*
@@ -46,211 +55,328 @@ macro_rules! def_machine {
   ) => {
 
     $crate::def_machine!{
-      @base
-      machine $machine
-        $(<$($type_var $(: { $($type_constraint),+ })*),+>)*
+      @validate ($)
+      STATES [
+        $($state ($($data_name),*))+
+      ]
+      EVENTS [
+        $($event <$source> $(=> <$target>)*
+          { $($($state_data),*)* }
+        )*
+      ]
+      initial_state: $initial
+      $(terminal_state: $terminal)*
       {
-        STATES [
-          $(state $state ($($data_name : $data_type $(= $data_default)*),*)
-          $({
-            $(entry $entry)*
-            $(exit  $exit)*
-          })*)+
-        ]
-        EVENTS [
-          $(event $event <$source> $(=> <$target>)*
-            ($($param_name : $param_type $(= $param_default)*),*)
-            $({$($state_data),*} => $action)*
-          )*
-        ]
-        EXTENDED [
-          $($ext_name : $ext_type $(= $ext_default)*),*
-        ]
-        $(self_reference: $self_reference)*
-        initial_state: $initial $({
-          $(initial_action: $initial_action)*
-        })*
-        $(terminal_state: $terminal $({
-          $(terminate_success: $terminate_success)*
-          $(terminate_failure: $terminate_failure)*
-        })*)*
-      }
-    }
+        $crate::def_machine!{
+          @base
+          machine $machine
+            $(<$($type_var $(: { $($type_constraint),+ })*),+>)*
+          {
+            STATES [
+              $(state $state ($($data_name : $data_type $(= $data_default)*),*)
+              $({
+                $(entry $entry)*
+                $(exit  $exit)*
+              })*)+
+            ]
+            EVENTS [
+              $(event $event <$source> $(=> <$target>)*
+                ($($param_name : $param_type $(= $param_default)*),*)
+                $({$($state_data),*} => $action)*
+              )*
+            ]
+            EXTENDED [
+              $($ext_name : $ext_type $(= $ext_default)*),*
+            ]
+            $(self_reference: $self_reference)*
+            $(logging: {
+              $(target: $log_target)?
+              $(initialize: $log_initialize)?
+              $(accepted: $log_accepted)?
+              $(rejected: $log_rejected)?
+              $(terminate_success: $log_terminate_success)?
+              $(terminate_failure: $log_terminate_failure)?
+            })?
+            $(fuzz: $($fuzz),+)?
+            initial_state: $initial $({
+              $(initial_action: $initial_action)*
+            })*
+            $(terminal_state: $terminal $({
+              $(terminate_success: $terminate_success)*
+              $(terminate_failure: $terminate_failure)*
+            })*)*
+          }
+        }
 
-    impl $(<$($type_var),+>)* $machine $(<$($type_var),+>)* where
-    $($(
-      $($($type_var : $type_constraint),+)*
-    ),+)*
-    {
-      pub fn initial() -> Self {
-        $crate::log::debug!(
-          machine=stringify!($machine), state=stringify!($initial);
-          "initialize state machine");
-        let mut extended_state = ExtendedState::initial();
-        let state = StateId::$initial.to_state (&mut extended_state);
-        let mut initial = Self { state, extended_state };
+        impl $(<$($type_var),+>)* $machine $(<$($type_var),+>)* where
+        $($(
+          $($($type_var : $type_constraint),+)*
+        ),+)*
         {
-          $(#[allow(unused_variables)]
-          let $self_reference = &mut initial;)*
-          $($($initial_action)*)*
+          pub fn initial() -> Self {
+            $crate::log::log!(
+              target: Self::LOG_CONFIG.target, Self::LOG_CONFIG.initialize,
+              machine=stringify!($machine), state=stringify!($initial);
+              "initialize state machine");
+            let mut extended_state = ExtendedState::initial();
+            let state = StateId::$initial.to_state (&mut extended_state);
+            let mut initial = Self {
+              state, extended_state, instance: None, metrics: None, coverage: None
+            };
+            {
+              $(#[allow(unused_variables)]
+              let $self_reference = &mut initial;)*
+              $($($initial_action)*)*
+            }
+            initial.state_entry();
+            initial
+          }
+
+          /// Run a new machine through the transition tour (see `paths::tour`)
+          /// using default event parameters and check the state after each event;
+          /// intended for use in tests
+          ///
+          /// # Panics
+          ///
+          /// Panics if an event is rejected or leads to an unexpected state.
+          // if no events are defined the event ID type is uninhabited and the loop
+          // body is unreachable
+          #[allow(dead_code, unreachable_code, unused_variables)]
+          pub fn assert_transition_tour() {
+            let mut machine = Self::initial();
+            for (step, (event_id, expected)) in
+              $crate::paths::tour::<Self>().into_iter().enumerate()
+            {
+              let state = machine.state_id();
+              if let Err (exception) = machine.handle_event (event_id.clone().into()) {
+                panic!("transition tour step {}: event {} rejected in state {}: {:?}",
+                  step, event_id.name(), state.name(), exception)
+              }
+              assert!(machine.state_id() == expected,
+                "transition tour step {}: event {} led from state {} to {} instead of {}",
+                step, event_id.name(), state.name(), machine.state_id().name(),
+                expected.name());
+            }
+          }
+
+          /// Run a new machine through a random walk of up to `steps` events with
+          /// default parameters chosen by a `SplitMix64` generator with the given
+          /// `seed`, checking the `invariant` after each event; intended for use in
+          /// tests. If the walk succeeds the machine is returned in the state the
+          /// walk left it in.
+          ///
+          /// # Panics
+          ///
+          /// Panics with the seed and event trace if an event is rejected, handling
+          /// an event panics or the invariant does not hold. The machine is leaked
+          /// instead of dropped so that its terminate actions can not panic while
+          /// the failure is reported.
+          #[allow(dead_code)]
+          pub fn assert_random_walk <I> (seed : u64, steps : usize, invariant : I)
+            -> Self
+          where
+            I : FnMut (&Self) -> Result <(), String>
+          {
+            let mut machine = Self::initial();
+            let mut rng = $crate::walk::SplitMix64::new (seed);
+            if let Err (failure) =
+              machine.random_walk (&mut rng, steps, |id, _| id.into(), invariant)
+            {
+              let _ = std::mem::ManuallyDrop::new (machine);
+              panic!("{}", failure)
+            }
+            machine
+          }
         }
-        initial.state_entry();
-        initial
-      }
-    }
 
-    impl $(<$($type_var),+>)* $crate::MachineDotfile
-      for $machine $(<$($type_var),+>)*
-    where
-    $($(
-      $($($type_var : $type_constraint),+)*
-    ),+)*
-    {
-      fn name() -> &'static str {
-        stringify!($machine)
-      }
-      fn type_vars() -> Vec <String> {
-        let mut _v = Vec::new();
+        impl $(<$($type_var),+>)* $crate::MachineDotfile
+          for $machine $(<$($type_var),+>)*
+        where
         $($(
-        _v.push (format!(
-          "{} = {}", stringify!($type_var), std::any::type_name::<$type_var>()));
-        )+)*
-        _v
-      }
-      fn extended_state_names() -> Vec <&'static str> {
-        vec![$(stringify!($ext_name)),*]
-      }
-      fn extended_state_types() -> Vec <&'static str> {
-        vec![$(stringify!($ext_type)),*]
-      }
-      fn extended_state_defaults() -> Vec <&'static str> {
-        let mut _v = Vec::new();
-        $(
-        _v.push ({
-          let default_expr = stringify!($($ext_default)*);
-          if !default_expr.is_empty() {
-            default_expr
-          } else {
-            concat!(stringify!($ext_type), "::default()")
+          $($($type_var : $type_constraint),+)*
+        ),+)*
+        {
+          fn name() -> &'static str {
+            stringify!($machine)
           }
-        });
-        )*
-        _v
-      }
-      fn self_reference() -> &'static str {
-        stringify!($($self_reference)*)
-      }
-      fn states() -> Vec <&'static str> {
-        vec![$(stringify!($state)),+]
-      }
-      fn state_initial() -> &'static str {
-        stringify!($initial)
-      }
-      fn state_terminal() -> &'static str {
-        stringify!($($terminal)*)
-      }
-      fn state_data_names() -> Vec <Vec <&'static str>> {
-        let mut v = Vec::new();
-        $({
-          let mut _w = Vec::new();
-          $(_w.push (stringify!($data_name));)*
-          v.push (_w);
-        })+
-        v
-      }
-      fn state_data_types() -> Vec <Vec <&'static str>> {
-        let mut v = Vec::new();
-        $({
-          let mut _w = Vec::new();
-          $(_w.push (stringify!($data_type));)*
-          v.push (_w);
-        })+
-        v
-      }
-      fn state_data_defaults() -> Vec <Vec <&'static str>> {
-        let mut v = Vec::new();
-        $({
-          let mut _w = Vec::new();
-          $(
-          _w.push ({
-            let default_expr = stringify!($($data_default)*);
-            if !default_expr.is_empty() {
-              default_expr
-            } else {
-              concat!(stringify!($data_type), "::default()")
+          fn type_vars() -> Vec <String> {
+            let mut _v = Vec::new();
+            $($(
+            _v.push (format!(
+              "{} = {}", stringify!($type_var), std::any::type_name::<$type_var>()));
+            )+)*
+            _v
+          }
+          fn extended_state_names() -> Vec <&'static str> {
+            vec![$(stringify!($ext_name)),*]
+          }
+          fn extended_state_types() -> Vec <&'static str> {
+            vec![$(stringify!($ext_type)),*]
+          }
+          fn extended_state_defaults() -> Vec <&'static str> {
+            let mut _v = Vec::new();
+            $(
+            _v.push ({
+              let default_expr = stringify!($($ext_default)*);
+              if !default_expr.is_empty() {
+                default_expr
+              } else {
+                concat!(stringify!($ext_type), "::default()")
+              }
+            });
+            )*
+            _v
+          }
+          fn self_reference() -> &'static str {
+            stringify!($($self_reference)*)
+          }
+          fn states() -> Vec <&'static str> {
+            vec![$(stringify!($state)),+]
+          }
+          fn state_initial() -> &'static str {
+            stringify!($initial)
+          }
+          fn state_terminal() -> &'static str {
+            stringify!($($terminal)*)
+          }
+          fn state_data_names() -> Vec <Vec <&'static str>> {
+            vec![$(vec![$(stringify!($data_name)),*]),+]
+          }
+          fn state_data_types() -> Vec <Vec <&'static str>> {
+            vec![$(vec![$(stringify!($data_type)),*]),+]
+          }
+          fn state_data_defaults() -> Vec <Vec <&'static str>> {
+            let mut v = Vec::new();
+            $({
+              let mut _w = Vec::new();
+              $(
+              _w.push ({
+                let default_expr = stringify!($($data_default)*);
+                if !default_expr.is_empty() {
+                  default_expr
+                } else {
+                  concat!(stringify!($data_type), "::default()")
+                }
+              });
+              )*
+              v.push (_w);
+            })+
+            v
+          }
+          /// &#9888; This function creates default values for each state data field
+          /// and creates a pretty printed string of the value
+          fn state_data_pretty_defaults() -> Vec <Vec <String>> {
+            let mut v = Vec::new();
+            $({
+              let mut _w = Vec::new();
+              $(
+              let default_val : $data_type
+                = $crate::def_machine!(@expr_default $($data_default)*);
+              _w.push (format!("{:#?}", default_val));
+              )*
+              v.push (_w);
+            })+
+            v
+          }
+          fn events() -> Vec <&'static str> {
+            vec![$(stringify!($event)),*]
+          }
+          fn event_sources() -> Vec <&'static str> {
+            vec![$(stringify!($source)),*]
+          }
+          fn event_targets() -> Vec <&'static str> {
+            vec![$(stringify!($($target)*)),*]
+          }
+          fn event_actions() -> Vec <&'static str> {
+            vec![$(stringify!($($action)*)),*]
+          }
+          fn event_param_names() -> Vec <Vec <&'static str>> {
+            vec![$(vec![$(stringify!($param_name)),*]),*]
+          }
+          fn event_param_types() -> Vec <Vec <&'static str>> {
+            vec![$(vec![$(stringify!($param_type)),*]),*]
+          }
+          fn event_param_defaults() -> Vec <Vec <&'static str>> {
+            vec![$(vec![$(stringify!($($param_default)*)),*]),*]
+          }
+          fn state_entry_actions() -> Vec <&'static str> {
+            vec![$(stringify!($($($entry)*)*)),+]
+          }
+          fn state_exit_actions() -> Vec <&'static str> {
+            vec![$(stringify!($($($exit)*)*)),+]
+          }
+          fn initial_action() -> &'static str {
+            stringify!($($($initial_action)*)*)
+          }
+          fn terminate_success_action() -> &'static str {
+            stringify!($($($($terminate_success)*)*)*)
+          }
+          fn terminate_failure_action() -> &'static str {
+            stringify!($($($($terminate_failure)*)*)*)
+          }
+        } // end impl MachineDotfile
+
+        impl $(<$($type_var),+>)* ExtendedState $(<$($type_var),+>)* where
+        $($(
+          $($($type_var : $type_constraint),+)*
+        ),+)*
+        {
+          pub fn initial() -> Self {
+            Self {
+              $($ext_name: $crate::def_machine!(@expr_default $($ext_default)*)),*
             }
-          });
-          )*
-          v.push (_w);
-        })+
-        v
-      }
-      /// &#9888; This function creates default values for each state data field
-      /// and creates a pretty printed string of the value
-      fn state_data_pretty_defaults() -> Vec <Vec <String>> {
-        let mut v = Vec::new();
-        $({
-          let mut _w = Vec::new();
-          $(
-          let default_val : $data_type
-            = $crate::def_machine!(@expr_default $($data_default)*);
-          _w.push (format!("{:#?}", default_val));
-          )*
-          v.push (_w);
-        })+
-        v
-      }
-      fn events() -> Vec <&'static str> {
-        vec![$(stringify!($event)),*]
-      }
-      fn event_sources() -> Vec <&'static str> {
-        vec![$(stringify!($source)),*]
-      }
-      fn event_targets() -> Vec <&'static str> {
-        vec![$(stringify!($($target)*)),*]
-      }
-      fn event_actions() -> Vec <&'static str> {
-        vec![$(stringify!($($action)*)),*]
-      }
-    } // end impl MachineDotfile
+          }
 
-    impl $(<$($type_var),+>)* ExtendedState $(<$($type_var),+>)* where
-    $($(
-      $($($type_var : $type_constraint),+)*
-    ),+)*
-    {
-      pub fn initial() -> Self {
-        Self {
-          $($ext_name: $crate::def_machine!(@expr_default $($ext_default)*)),*
+          /// Creation method that allows overriding defaults.
+          pub fn new ($($ext_name : Option <$ext_type>),*) -> Self {
+            Self {
+              $($ext_name: $ext_name.unwrap_or (
+                $crate::def_machine!(@expr_default $($ext_default)*))
+              ),*
+            }
+          }
         }
-      }
 
-      /// Creation method that allows overriding defaults.
-      pub fn new ($($ext_name : Option <$ext_type>),*) -> Self {
-        Self {
-          $($ext_name: $ext_name.unwrap_or (
-            $crate::def_machine!(@expr_default $($ext_default)*))
-          ),*
+        impl <'event> Event <'event> {
+          /// Shortest sequence of events with default parameters leading from
+          /// state `from` to state `to`, or `None` if `to` can not be reached
+          #[allow(dead_code)]
+          pub fn path (from : &StateId, to : &StateId) -> Option <Vec <Self>> {
+            $crate::paths::shortest_in (StateId::ALL, EventId::TRANSITIONS,
+              from, to
+            ).map (|path| path.into_iter().map (Self::from).collect())
+          }
+
+          /// Shortest sequences of events with default parameters leading from the
+          /// initial state to each reachable state
+          #[allow(dead_code)]
+          pub fn paths() -> Vec <(StateId, Vec <Self>)> {
+            $crate::paths::from_state_in (StateId::ALL, EventId::TRANSITIONS,
+              &StateId::initial()
+            ).into_iter().map (|(state, path)|
+              (state, path.into_iter().map (Self::from).collect())
+            ).collect()
+          }
         }
-      }
-    }
 
-    impl <'event> From <EventId> for Event <'event> {
-      /// Construct an event with default parameters for the given ID
-      fn from (id : EventId) -> Self {
-        let _params = id.clone().into();
-        Event { id, params: _params }
-      }
-    }
+        impl <'event> From <EventId> for Event <'event> {
+          /// Construct an event with default parameters for the given ID
+          fn from (id : EventId) -> Self {
+            let _params = id.clone().into();
+            Event { id, params: _params }
+          }
+        }
 
-    impl <'event> From <EventId> for EventParams <'event> {
-      fn from (id : EventId) -> Self {
-        match id {
-          $(EventId::$event => EventParams::$event {
-            $($param_name:
-              $crate::def_machine!(@expr_default $($param_default)*)
-            ),*
-          }),*
+        impl <'event> From <EventId> for EventParams <'event> {
+          fn from (id : EventId) -> Self {
+            match id {
+              $(EventId::$event => EventParams::$event {
+                $($param_name:
+                  $crate::def_machine!(@expr_default $($param_default)*)
+                ),*
+              }),*
+            }
+          }
         }
       }
     }
@@ -284,6 +410,15 @@ macro_rules! def_machine {
           $({ $($state_data:ident),* } => $action:block)*
         )*
       ]
+      $(logging: {
+        $(target: $log_target:literal)?
+        $(initialize: $log_initialize:ident)?
+        $(accepted: $log_accepted:ident)?
+        $(rejected: $log_rejected:ident)?
+        $(terminate_success: $log_terminate_success:ident)?
+        $(terminate_failure: $log_terminate_failure:ident)?
+      })?
+      $(fuzz: $($fuzz:ident),+)?
       initial_state: $initial:ident $({
         $(initial_action: $initial_action:block)*
       })*
@@ -314,6 +449,15 @@ macro_rules! def_machine {
           $($($ext_name : $ext_type $(= $ext_default)*),*)*
         ]
         $(self_reference: $self_reference)*
+        $(logging: {
+          $(target: $log_target)?
+          $(initialize: $log_initialize)?
+          $(accepted: $log_accepted)?
+          $(rejected: $log_rejected)?
+          $(terminate_success: $log_terminate_success)?
+          $(terminate_failure: $log_terminate_failure)?
+        })?
+        $(fuzz: $($fuzz),+)?
         initial_state: $initial $({
           $(initial_action: $initial_action)*
         })*
@@ -353,14 +497,23 @@ macro_rules! def_machine {
       #[allow(unreachable_code)]
       match _event.transition() {
         Transition::Universal (target_id) => {
-          $crate::log::debug!(
-            machine=stringify!($machine), state:?=self.state.id,
+          $crate::log::log!(
+            target: Self::LOG_CONFIG.target, Self::LOG_CONFIG.accepted,
+            machine=stringify!($machine), instance=self.instance_name(),
+            state:?=self.state.id,
             event:?=_event.id, transition="Universal", target:?=target_id;
             "handle state machine event");
+          if let Some (metrics) = self.metrics.as_mut() {
+            metrics.event_accepted (_event.id.clone() as usize);
+          }
+          if let Some (coverage) = self.coverage.as_mut() {
+            coverage.event_handled (
+              self.state.id.clone() as usize, _event.id.clone() as usize);
+          }
           self.state_exit();
           { // event action
             // bring extended state variables into scope
-            #[allow(unused_variables)]
+            #[allow(unused_mut, unused_variables)]
             match &mut self.extended_state {
               &mut ExtendedState { $(ref mut $ext_name,)*.. } => {
                 // map each event to an action
@@ -384,12 +537,21 @@ macro_rules! def_machine {
         }
         Transition::Internal (source_id) => {
           if self.state.id == source_id {
-            $crate::log::debug!(
-              machine=stringify!($machine), state:?=self.state.id,
+            $crate::log::log!(
+              target: Self::LOG_CONFIG.target, Self::LOG_CONFIG.accepted,
+              machine=stringify!($machine), instance=self.instance_name(),
+              state:?=self.state.id,
               event:?=_event.id, transition="Internal", source_state:?=source_id;
               "handle state machine event");
+            if let Some (metrics) = self.metrics.as_mut() {
+              metrics.event_accepted (_event.id.clone() as usize);
+            }
+            if let Some (coverage) = self.coverage.as_mut() {
+              coverage.event_handled (
+                self.state.id.clone() as usize, _event.id.clone() as usize);
+            }
             // bring extended state variables into scope
-            #[allow(unused_variables)]
+            #[allow(unused_mut, unused_variables)]
             match &mut self.extended_state {
               &mut ExtendedState { $(ref mut $ext_name,)*.. } => {
                 // map each event to an action
@@ -421,25 +583,39 @@ macro_rules! def_machine {
             Ok (())
           } else {
             let exception = $crate::HandleEventException::WrongState;
-            $crate::log::warn!(
-              machine=stringify!($machine), state:?=self.state.id,
+            $crate::log::log!(
+              target: Self::LOG_CONFIG.target, Self::LOG_CONFIG.rejected,
+              machine=stringify!($machine), instance=self.instance_name(),
+              state:?=self.state.id,
               event:?=_event.id, transition="Internal", source:?=source_id,
               exception:?;
               "handle state machine event exception");
+            if let Some (metrics) = self.metrics.as_mut() {
+              metrics.event_rejected (_event.id.clone() as usize);
+            }
             Err (exception)
           }
         }
         Transition::External (source_id, target_id) => {
           if self.state.id == source_id {
-            $crate::log::debug!(
-              machine=stringify!($machine), state:?=self.state.id,
+            $crate::log::log!(
+              target: Self::LOG_CONFIG.target, Self::LOG_CONFIG.accepted,
+              machine=stringify!($machine), instance=self.instance_name(),
+              state:?=self.state.id,
               event:?=_event.id, transition="External", source:?=source_id,
               target:?=target_id;
               "handle state machine event");
+            if let Some (metrics) = self.metrics.as_mut() {
+              metrics.event_accepted (_event.id.clone() as usize);
+            }
+            if let Some (coverage) = self.coverage.as_mut() {
+              coverage.event_handled (
+                self.state.id.clone() as usize, _event.id.clone() as usize);
+            }
             self.state_exit();
             { // event action
               // bring extended state variables into scope
-              #[allow(unused_variables)]
+              #[allow(unused_mut, unused_variables)]
               match &mut self.extended_state {
                 &mut ExtendedState { $(ref mut $ext_name,)*.. } => {
                   // map each event to an action
@@ -462,11 +638,16 @@ macro_rules! def_machine {
             Ok (())
           } else {
             let exception = $crate::HandleEventException::WrongState;
-            $crate::log::warn!(
-              machine=stringify!($machine), state:?=self.state.id,
+            $crate::log::log!(
+              target: Self::LOG_CONFIG.target, Self::LOG_CONFIG.rejected,
+              machine=stringify!($machine), instance=self.instance_name(),
+              state:?=self.state.id,
               event:?=_event.id, transition="External", source:?=source_id,
               target:?=target_id, exception:?;
               "handle state machine event exception");
+            if let Some (metrics) = self.metrics.as_mut() {
+              metrics.event_rejected (_event.id.clone() as usize);
+            }
             Err (exception)
           }
         }
@@ -569,6 +750,27 @@ macro_rules! def_machine {
     Transition::Universal (StateId::$target)
   };
 
+  //
+  //  @transition_row: external
+  //
+  ( @transition_row $event:ident <$source:ident> => <$target:ident> ) => {
+    (EventId::$event, Some (StateId::$source), Some (StateId::$target))
+  };
+
+  //
+  //  @transition_row: internal
+  //
+  ( @transition_row $event:ident <$source:ident> ) => {
+    (EventId::$event, Some (StateId::$source), None)
+  };
+
+  //
+  //  @transition_row: universal
+  //
+  ( @transition_row $event:ident <*> => <$target:ident> ) => {
+    (EventId::$event, None, Some (StateId::$target))
+  };
+
   //
   //  @expr_default: override default
   //
@@ -589,6 +791,159 @@ macro_rules! def_machine {
   //
   ( @expr_option ) => { None };
 
+  //
+  //  @log_target: default
+  //
+  ( @log_target ) => { module_path!() };
+
+  //
+  //  @log_target: override default
+  //
+  ( @log_target $target:literal ) => { $target };
+
+  //
+  //  @log_level: default
+  //
+  ( @log_level $default:ident ) => { $crate::log::Level::$default };
+
+  //
+  //  @log_level: override default
+  //
+  ( @log_level $default:ident $level:ident ) => { $crate::log::Level::$level };
+
+  //
+  //  @fuzz_target: check the names given in the `fuzz` clause
+  //
+  ( @fuzz_target arbitrary ) => {};
+  ( @fuzz_target proptest ) => {};
+  ( @fuzz_target $other:ident ) => {
+    compile_error!(concat!("unknown fuzz target `", stringify!($other),
+      "`, expected `arbitrary` or `proptest`"));
+  };
+
+  //
+  //  @validate: compile-time checks of state, event and local variable names,
+  //  expanding to the continuation block only if all checks pass; the `$d`
+  //  argument is a literal `$` used to define the nested checking macros
+  //
+  ( @validate ($d:tt)
+    STATES [
+      $($state:ident ($($data_name:ident),*))+
+    ]
+    EVENTS [
+      $($event:ident <$source:tt> $(=> <$target:ident>)*
+        { $($state_data:ident),* }
+      )*
+    ]
+    initial_state: $initial:ident
+    $(terminal_state: $terminal:ident)*
+    { $($continuation:tt)* }
+  ) => {
+    // not every rule is used by every machine
+    #[allow(unused_macros, unused_macro_rules)]
+    const _ : () = {
+      macro_rules! check_state {
+        $(
+          (event $d event:ident $d kind:literal $state) => {};
+          ($d kind:ident $state) => {};
+        )+
+        (event $d event:ident $d kind:literal *) => {};
+        (event $d event:ident $d kind:literal $d other:ident) => {
+          compile_error!(concat!("event `", stringify!($d event), "`: undefined ",
+            $d kind, " state `", stringify!($d other), "`"));
+        };
+        ($d kind:ident $d other:ident) => {
+          compile_error!(concat!("undefined ", stringify!($d kind),
+            " state `", stringify!($d other), "`"));
+        };
+      }
+      macro_rules! check_data {
+        $($(
+          ($d event:ident $state $data_name) => {};
+        )*)+
+        ($d event:ident * $d data:ident) => {};
+        ($d event:ident $d state:ident $d data:ident) => {
+          compile_error!(concat!("event `", stringify!($d event), "`: state `",
+            stringify!($d state), "` has no local variable `",
+            stringify!($d data), "`"));
+        };
+      }
+      $(
+        check_state!(event $event "source" $source);
+        $(check_state!(event $event "target" $target);)*
+        $(check_data!($event $source $state_data);)*
+      )*
+      check_state!(initial $initial);
+      $(check_state!(terminal $terminal);)*
+    };
+    // the errors are reported above: the continuation is only expanded if
+    // every referenced name matches a definition
+    #[allow(unused_macros, unused_macro_rules)]
+    macro_rules! __macro_machines_checked {
+      ( [$d($($d($state)?)+ $d(*)? ;)*]
+        [$d($($d($state)?)+ ;)*]
+        [$d($($d(($state $($d($data_name)?)*))?)+ $d((* $d _data:ident))? ;)*]
+        { $d($d continuation:tt)* }
+      ) => {
+        $d($d continuation)*
+      };
+      ($d($d _unchecked:tt)*) => {};
+    }
+    // defines a rule for each name that matches the name list only if it
+    // contains the name twice, skipping the other names without recursion so
+    // that any number of states and events can be checked; the checked
+    // continuation is only expanded if no rule matches
+    macro_rules! __macro_machines_unique {
+      ( $d d:tt [$d($d s:ident)+] [$d($d e:ident)*]
+        { $d($d checked:tt)* }
+      ) => {
+        #[allow(unused_macro_rules)]
+        macro_rules! __macro_machines_unique_checked {
+          $d(
+            ( { $d d($d d _checked:tt)* }
+              [ $d d($($d d($state)?)+ ;)* $d s ;
+                $d d($($d d($state)?)+ ;)* $d s ;
+                $d d($($d d($state)?)+ ;)* ]
+              [ $d d($($d d($event)?)* ;)* ]
+            ) => {
+              compile_error!(concat!("duplicate state `", stringify!($d s), "`"));
+            };
+          )+
+          $d(
+            ( { $d d($d d _checked:tt)* }
+              [ $d d($($d d($state)?)+ ;)* ]
+              [ $d d($($d d($event)?)* ;)* $d e ;
+                $d d($($d d($event)?)* ;)* $d e ;
+                $d d($($d d($event)?)* ;)* ]
+            ) => {
+              compile_error!(concat!("duplicate event `", stringify!($d e), "`"));
+            };
+          )*
+          ( { $d d($d d checked:tt)* }
+            [ $d d($($d d($state)?)+ ;)* ]
+            [ $d d($($d d($event)?)* ;)* ]
+          ) => {
+            $d d($d d checked)*
+          };
+        }
+        __macro_machines_unique_checked!{
+          { $d($d checked)* } [$d($d s ;)+] [$d($d e ;)*]
+        }
+      };
+    }
+    __macro_machines_unique!{
+      $d [$($state)+] [$($event)*]
+      {
+        __macro_machines_checked!{
+          [$($source ;)*]
+          [$($($target ;)*)* $initial ; $($terminal ;)*]
+          [$($(($source $state_data) ;)*)*]
+          { $($continuation)* }
+        }
+      }
+    }
+  };
+
   //
   //  @base implementation rule
   //
@@ -616,6 +971,15 @@ macro_rules! def_machine {
         $($ext_name:ident : $ext_type:ty $(= $ext_default:expr)*),*
       ]
       $(self_reference: $self_reference:ident)*
+      $(logging: {
+        $(target: $log_target:literal)?
+        $(initialize: $log_initialize:ident)?
+        $(accepted: $log_accepted:ident)?
+        $(rejected: $log_rejected:ident)?
+        $(terminate_success: $log_terminate_success:ident)?
+        $(terminate_failure: $log_terminate_failure:ident)?
+      })?
+      $(fuzz: $($fuzz:ident),+)?
       initial_state: $initial:ident $({
         $(initial_action: $initial_action:block)*
       })*
@@ -627,13 +991,25 @@ macro_rules! def_machine {
 
   ) => {
 
+    $($($crate::def_machine!(@fuzz_target $fuzz);)+)?
+    $crate::def_machine_arbitrary!{
+      [$($($fuzz)+)?]
+      EVENTS [
+        $($event ($($param_name : $param_type),*))*
+      ]
+    }
+    $crate::def_machine_proptest!([$($($fuzz)+)?] @event_id);
+
     pub struct $machine $(<$($type_var),+>)* where
     $($(
       $($($type_var : $type_constraint),+)*
     ),+)*
     {
       state          : State,
-      extended_state : ExtendedState $(<$($type_var),+>)*
+      extended_state : ExtendedState $(<$($type_var),+>)*,
+      instance       : Option <String>,
+      metrics        : Option <Box <$crate::metrics::Collector>>,
+      coverage       : Option <Box <$crate::coverage::Tracker>>
     }
 
     pub struct State {
@@ -689,6 +1065,21 @@ macro_rules! def_machine {
       $($($type_var : $type_constraint),+)*
     ),+)*
     {
+      /// Log target and levels for this state machine
+      pub const LOG_CONFIG : $crate::LogConfig = $crate::LogConfig {
+        target:            $crate::def_machine!(@log_target $($($log_target)?)?),
+        initialize:        $crate::def_machine!(
+          @log_level Debug $($($log_initialize)?)?),
+        accepted:          $crate::def_machine!(
+          @log_level Debug $($($log_accepted)?)?),
+        rejected:          $crate::def_machine!(
+          @log_level Warn $($($log_rejected)?)?),
+        terminate_success: $crate::def_machine!(
+          @log_level Debug $($($log_terminate_success)?)?),
+        terminate_failure: $crate::def_machine!(
+          @log_level Warn $($($log_terminate_failure)?)?)
+      };
+
       pub fn report_sizes() where $($($type_var : 'static),+)* {
         let machine_name = stringify!($machine);
         let machine_type = std::any::type_name::<Self>();
@@ -702,7 +1093,9 @@ macro_rules! def_machine {
         -> Self
       {
         let state   = StateId::$initial.to_state (&mut extended_state);
-        let mut new = Self { state, extended_state };
+        let mut new = Self {
+          state, extended_state, instance: None, metrics: None, coverage: None
+        };
         {
           $(#[allow(unused_variables)]
           let $self_reference = &mut new;)*
@@ -744,6 +1137,168 @@ macro_rules! def_machine {
         &mut self.extended_state
       }
 
+      /// Name or ID of this instance included in log records
+      #[allow(dead_code)]
+      #[inline]
+      pub fn instance_name (&self) -> Option <&str> {
+        self.instance.as_deref()
+      }
+
+      /// Set a name or ID of this instance to be included in subsequent log
+      /// records
+      #[allow(dead_code)]
+      #[inline]
+      pub fn set_instance_name (&mut self, name : String) {
+        self.instance = Some (name);
+      }
+
+      /// Whether the given event would be accepted in the current state
+      #[allow(dead_code)]
+      pub fn can_handle (&self, event_id : &EventId) -> bool {
+        match event_id.transition() {
+          Transition::Universal (_) => true,
+          Transition::Internal (source_id) |
+          Transition::External (source_id, _) => self.state.id == source_id
+        }
+      }
+
+      /// Describe the state exit, event and state entry actions that would be
+      /// performed and the resulting state if the given event were handled in
+      /// the current state, without modifying the machine or running any
+      /// actions
+      // if no events are defined the event ID type is uninhabited and the body
+      // is unreachable
+      #[allow(dead_code, unreachable_code, unused_variables)]
+      pub fn preview (&self, event : &Event) -> Result <
+        $crate::TransitionPlan <StateId, EventId>, $crate::HandleEventException
+      > {
+        let event_action = Some (event.id.clone())
+          .filter (EventId::has_action);
+        let (exit_action, entry_action, target) = match event.transition() {
+          Transition::Universal (target_id) => (
+            Some (self.state.id.clone()).filter (StateId::has_exit_action),
+            Some (target_id.clone()).filter (StateId::has_entry_action),
+            target_id
+          ),
+          Transition::Internal (source_id) if self.state.id == source_id =>
+            (None, None, source_id),
+          Transition::External (source_id, target_id)
+            if self.state.id == source_id
+          => (
+            Some (source_id).filter (StateId::has_exit_action),
+            Some (target_id.clone()).filter (StateId::has_entry_action),
+            target_id
+          ),
+          _ => return Err ($crate::HandleEventException::WrongState)
+        };
+        Ok ($crate::TransitionPlan {
+          event: event.id.clone(), exit_action, event_action, entry_action, target
+        })
+      }
+
+      /// Events that would be accepted in the current state, in order of
+      /// definition
+      #[allow(dead_code)]
+      pub fn enabled_events (&self) -> impl Iterator <Item=EventId> + '_ {
+        EventId::ALL.iter().filter (|id| self.can_handle (id)).cloned()
+      }
+
+      /// Generate a DOT file for the machine with the current state
+      /// highlighted and the enabled events marked; the values of the extended
+      /// state and state data are only shown by `_debug` machines
+      #[allow(dead_code)]
+      pub fn dotfile_instance (&self) -> String {
+        let enabled : Vec <bool> = EventId::ALL.iter()
+          .map (|id| self.can_handle (id)).collect();
+        $crate::instance_dotfile::<Self> (self.state_id().name(), None, None,
+          &enabled)
+      }
+
+      /// Handle up to `steps` events chosen at random among the enabled events,
+      /// with parameters created by `event`, checking the `invariant` after each
+      /// step (see `walk::run`)
+      // if no events are defined the event ID type is uninhabited and the
+      // event handler is unreachable
+      #[allow(dead_code, unreachable_code, unused_variables)]
+      pub fn random_walk <'event, R, F, I> (&mut self,
+        rng       : &mut R,
+        steps     : usize,
+        mut event : F,
+        invariant : I
+      ) -> Result <(), $crate::walk::Failure <EventId>> where
+        R : $crate::walk::Rng,
+        F : FnMut (EventId, &mut R) -> Event <'event>,
+        I : FnMut (&Self) -> Result <(), String>
+      {
+        $crate::walk::run (self, rng, steps,
+          |machine| machine.enabled_events().collect(),
+          |machine, id, rng| machine.handle_event (event (id.clone(), rng)),
+          invariant)
+      }
+
+      /// Start collecting transition metrics for this instance, replacing any
+      /// previously collected metrics.
+      ///
+      /// The current state is counted as entered when collection starts.
+      #[allow(dead_code)]
+      pub fn enable_metrics (&mut self,
+        clock : Box <dyn $crate::metrics::Clock + Send + Sync>
+      ) {
+        let mut metrics = $crate::metrics::Collector::new (
+          clock, StateId::VARIANT_COUNT, EventId::VARIANT_COUNT);
+        metrics.state_entered (self.state.id.clone() as usize);
+        self.metrics = Some (Box::new (metrics));
+      }
+
+      /// Stop collecting transition metrics and discard collected metrics
+      #[allow(dead_code)]
+      pub fn disable_metrics (&mut self) {
+        self.metrics = None;
+      }
+
+      /// Snapshot of collected transition metrics, or `None` if metrics
+      /// collection is not enabled
+      #[allow(dead_code)]
+      pub fn metrics (&self) -> Option <$crate::metrics::Snapshot> {
+        use $crate::MachineDotfile;
+        self.metrics.as_ref().map (|metrics| metrics.snapshot (
+          Self::name(), self.instance_name(), &Self::states(), &Self::events()))
+      }
+
+      /// Start tracking transition coverage for this instance, replacing any
+      /// previously tracked coverage
+      #[allow(dead_code)]
+      pub fn enable_coverage (&mut self) {
+        self.coverage = Some (Box::new ($crate::coverage::Tracker::new (
+          StateId::VARIANT_COUNT, EventId::VARIANT_COUNT)));
+      }
+
+      /// Stop tracking transition coverage and discard tracked coverage
+      #[allow(dead_code)]
+      pub fn disable_coverage (&mut self) {
+        self.coverage = None;
+      }
+
+      /// Report of tracked transition coverage, or `None` if coverage tracking
+      /// is not enabled
+      #[allow(dead_code)]
+      pub fn coverage (&self) -> Option <$crate::coverage::Report> {
+        use $crate::MachineDotfile;
+        self.coverage.as_ref().map (|coverage| {
+          let transitions : Vec <_> = EventId::TRANSITIONS.iter()
+            .map (|(event, source, target)| (
+              event.name(),
+              source.as_ref().map (StateId::name),
+              target.as_ref().map (StateId::name)
+            )).collect();
+          let states : Vec <_> = StateId::ALL.iter()
+            .map (|state|
+              (state.name(), state.has_entry_action(), state.has_exit_action()))
+            .collect();
+          coverage.report (Self::name(), &transitions, &states)
+        })
+      }
+
       $crate::def_machine!{
         @impl_fn_handle_event
         machine $machine {
@@ -761,6 +1316,12 @@ macro_rules! def_machine {
       }
 
       fn state_entry (&mut self) {
+        if let Some (metrics) = self.metrics.as_mut() {
+          metrics.state_entered (self.state.id.clone() as usize);
+        }
+        if let Some (coverage) = self.coverage.as_mut() {
+          coverage.state_entered (self.state.id.clone() as usize);
+        }
         // bring extended state variables into scope
         #[allow(unused_variables)]
         match &mut self.extended_state {
@@ -775,6 +1336,12 @@ macro_rules! def_machine {
         }
       }
       fn state_exit (&mut self) {
+        if let Some (metrics) = self.metrics.as_mut() {
+          metrics.state_exited();
+        }
+        if let Some (coverage) = self.coverage.as_mut() {
+          coverage.state_exited (self.state.id.clone() as usize);
+        }
         // bring extended state variables into scope
         #[allow(unused_variables)]
         match &mut self.extended_state {
@@ -825,25 +1392,62 @@ macro_rules! def_machine {
       fn drop (&mut self) {
         self.state_exit();
         let _state_id = self.state.id.clone();
+        $(
+        if _state_id != StateId::$terminal {
+          $crate::log::log!(
+            target: Self::LOG_CONFIG.target, Self::LOG_CONFIG.terminate_failure,
+            machine=stringify!($machine), instance=self.instance_name(),
+            state:?=_state_id, terminal:?=StateId::$terminal;
+            "terminate state machine failure: not in terminal state");
+        } else {
+          $crate::log::log!(
+            target: Self::LOG_CONFIG.target, Self::LOG_CONFIG.terminate_success,
+            machine=stringify!($machine), instance=self.instance_name(),
+            state:?=_state_id;
+            "terminate state machine success");
+        }
+        )*
+        // log records borrow the instance name so the self reference is
+        // introduced after logging
         $(#[allow(unused_variables)]
         let $self_reference = &mut *self;)*
         $(
         if _state_id != StateId::$terminal {
-          $crate::log::warn!(
-            machine=stringify!($machine), state:?=_state_id,
-            terminal:?=StateId::$terminal;
-            "terminate state machine failure: not in terminal state");
           $($($terminate_failure)*)*
         } else {
-          $crate::log::debug!(
-            machine=stringify!($machine), state:?=_state_id;
-            "terminate state machine success");
           $($($terminate_success)*)*
         }
         )*
       }
     }
 
+    impl $(<$($type_var),+>)* $crate::MachineMetadata
+      for $machine $(<$($type_var),+>)*
+    where
+    $($(
+      $($($type_var : $type_constraint),+)*
+    ),+)*
+    {
+      type StateId = StateId;
+      type EventId = EventId;
+      const STATES : &'static [StateId] = StateId::ALL;
+      const EVENTS : &'static [EventId] = EventId::ALL;
+      const TRANSITIONS
+        : &'static [(EventId, Option <StateId>, Option <StateId>)]
+        = EventId::TRANSITIONS;
+      const STATE_INITIAL  : StateId = StateId::$initial;
+      const STATE_TERMINAL : Option <StateId> =
+        $crate::def_machine!(@expr_option $(StateId::$terminal)*);
+      #[inline]
+      fn state_name (id : &StateId) -> &'static str {
+        id.name()
+      }
+      #[inline]
+      fn event_name (id : &EventId) -> &'static str {
+        id.name()
+      }
+    }
+
     impl State {
       #[inline]
       pub fn id (&self) -> &StateId {
@@ -857,10 +1461,34 @@ macro_rules! def_machine {
     }
 
     impl StateId {
+      /// All state IDs in order of definition
+      pub const ALL : &'static [StateId] = &[$(StateId::$state),+];
+
       #[inline]
-      pub const fn initial() -> Self {
-        StateId::$initial
-      }
+      pub const fn name (&self) -> &'static str {
+        match *self {
+          $(StateId::$state => stringify!($state)),+
+        }
+      }
+
+      /// Whether the state has an entry action
+      pub const fn has_entry_action (&self) -> bool {
+        match *self {
+          $(StateId::$state => $crate::is_action (stringify!($($($entry)*)*))),+
+        }
+      }
+
+      /// Whether the state has an exit action
+      pub const fn has_exit_action (&self) -> bool {
+        match *self {
+          $(StateId::$state => $crate::is_action (stringify!($($($exit)*)*))),+
+        }
+      }
+
+      #[inline]
+      pub const fn initial() -> Self {
+        StateId::$initial
+      }
       $(
       #[inline]
       pub const fn terminal() -> Self {
@@ -897,6 +1525,33 @@ macro_rules! def_machine {
     }
 
     impl EventId {
+      /// All event IDs in order of definition
+      pub const ALL : &'static [EventId] = &[$(EventId::$event),*];
+
+      /// Event, source and target state of each transition in order of
+      /// definition; see `MachineMetadata::TRANSITIONS`
+      pub const TRANSITIONS
+        : &'static [(EventId, Option <StateId>, Option <StateId>)]
+        = &[$(
+          $crate::def_machine!(@transition_row $event <$source> $(=> <$target>)*)
+        ),*];
+
+      #[inline]
+      #[allow(clippy::uninhabited_references)]
+      pub const fn name (&self) -> &'static str {
+        match *self {
+          $(EventId::$event => stringify!($event)),*
+        }
+      }
+
+      /// Whether the event has an action
+      #[allow(clippy::uninhabited_references)]
+      pub const fn has_action (&self) -> bool {
+        match *self {
+          $(EventId::$event => $crate::is_action (stringify!($($action)*))),*
+        }
+      }
+
       pub fn transition (&self) -> Transition {
         #[allow(clippy::uninhabited_references)]
         match *self {
@@ -976,6 +1631,15 @@ macro_rules! def_machine_nodefault {
         $($ext_name:ident : $ext_type:ty $(= $ext_default:expr)*),*
       ]
       $(self_reference: $self_reference:ident)*
+      $(logging: {
+        $(target: $log_target:literal)?
+        $(initialize: $log_initialize:ident)?
+        $(accepted: $log_accepted:ident)?
+        $(rejected: $log_rejected:ident)?
+        $(terminate_success: $log_terminate_success:ident)?
+        $(terminate_failure: $log_terminate_failure:ident)?
+      })?
+      $(fuzz: $($fuzz:ident),+)?
       initial_state: $initial:ident $({
         $(initial_action: $initial_action:block)*
       })*
@@ -988,160 +1652,196 @@ macro_rules! def_machine_nodefault {
   ) => {
 
     $crate::def_machine!{
-      @base
-      machine $machine
-        $(<$($type_var $(: { $($type_constraint),+ })*),+>)*
+      @validate ($)
+      STATES [
+        $($state ($($data_name),*))+
+      ]
+      EVENTS [
+        $($event <$source> $(=> <$target>)*
+          { $($($state_data),*)* }
+        )*
+      ]
+      initial_state: $initial
+      $(terminal_state: $terminal)*
       {
-        STATES [
-          $(state $state ($($data_name : $data_type $(= $data_default)*),*)
-          $({
-            $(entry $entry)*
-            $(exit  $exit)*
-          })*)+
-        ]
-        EVENTS [
-          $(event $event <$source> $(=> <$target>)*
-            ($($param_name : $param_type $(= $param_default)*),*)
-            $({$($state_data),*} => $action)*
-          )*
-        ]
-        EXTENDED [
-          $($ext_name : $ext_type $(= $ext_default)*),*
-        ]
-        $(self_reference: $self_reference)*
-        initial_state: $initial $({
-          $(initial_action: $initial_action)*
-        })*
-        $(terminal_state: $terminal $({
-          $(terminate_success: $terminate_success)*
-          $(terminate_failure: $terminate_failure)*
-        })*)*
-      }
-    }
+        $crate::def_machine!{
+          @base
+          machine $machine
+            $(<$($type_var $(: { $($type_constraint),+ })*),+>)*
+          {
+            STATES [
+              $(state $state ($($data_name : $data_type $(= $data_default)*),*)
+              $({
+                $(entry $entry)*
+                $(exit  $exit)*
+              })*)+
+            ]
+            EVENTS [
+              $(event $event <$source> $(=> <$target>)*
+                ($($param_name : $param_type $(= $param_default)*),*)
+                $({$($state_data),*} => $action)*
+              )*
+            ]
+            EXTENDED [
+              $($ext_name : $ext_type $(= $ext_default)*),*
+            ]
+            $(self_reference: $self_reference)*
+            $(logging: {
+              $(target: $log_target)?
+              $(initialize: $log_initialize)?
+              $(accepted: $log_accepted)?
+              $(rejected: $log_rejected)?
+              $(terminate_success: $log_terminate_success)?
+              $(terminate_failure: $log_terminate_failure)?
+            })?
+            $(fuzz: $($fuzz),+)?
+            initial_state: $initial $({
+              $(initial_action: $initial_action)*
+            })*
+            $(terminal_state: $terminal $({
+              $(terminate_success: $terminate_success)*
+              $(terminate_failure: $terminate_failure)*
+            })*)*
+          }
+        }
 
-    impl $(<$($type_var),+>)* $crate::MachineDotfile
-      for $machine $(<$($type_var),+>)*
-    where
-    $($(
-      $($($type_var : $type_constraint),+)*
-    ),+)*
-    {
-      fn name() -> &'static str {
-        stringify!($machine)
-      }
-      fn type_vars() -> Vec <String> {
-        let mut _v = Vec::new();
+        impl $(<$($type_var),+>)* $crate::MachineDotfile
+          for $machine $(<$($type_var),+>)*
+        where
         $($(
-        _v.push (format!(
-        "{} = {}", stringify!($type_var), std::any::type_name::<$type_var>()));
-        )+)*
-        _v
-      }
-      fn extended_state_names() -> Vec <&'static str> {
-        let mut _v = Vec::new();
-        $(
-        _v.push (stringify!($ext_name));
-        )*
-        _v
-      }
-      fn extended_state_types() -> Vec <&'static str> {
-        let mut _v = Vec::new();
-        $(
-        _v.push (stringify!($ext_type));
-        )*
-        _v
-      }
-      fn extended_state_defaults() -> Vec <&'static str> {
-        let mut _v = Vec::new();
-        $(
-        _v.push (stringify!($($ext_default)*));
-        )*
-        _v
-      }
-      fn self_reference() -> &'static str {
-        stringify!($($self_reference)*)
-      }
-      fn states() -> Vec <&'static str> {
-        vec![$(stringify!($state)),+]
-      }
-      fn state_initial() -> &'static str {
-        stringify!($initial)
-      }
-      fn state_terminal() -> &'static str {
-        stringify!($($terminal)*)
-      }
-      fn state_data_names() -> Vec <Vec <&'static str>> {
-        let mut v = Vec::new();
-        $({
-          let mut _w = Vec::new();
-          $(_w.push (stringify!($data_name));)*
-          v.push (_w);
-        })+
-        v
-      }
-      fn state_data_types() -> Vec <Vec <&'static str>> {
-        let mut v = Vec::new();
-        $({
-          let mut _w = Vec::new();
-          $(_w.push (stringify!($data_type));)*
-          v.push (_w);
-        })+
-        v
-      }
-      fn state_data_defaults() -> Vec <Vec <&'static str>> {
-        let mut v = Vec::new();
-        $({
-          let mut _w = Vec::new();
-          $(_w.push (stringify!($($data_default)*));)*
-          v.push (_w);
-        })+
-        v
-      }
-      /// This version does not evaluate expressions, only pretty prints them
-      fn state_data_pretty_defaults() -> Vec <Vec <String>> {
-        let mut v = Vec::new();
-        $({
-          let mut _w = Vec::new();
-          $(
-          _w.push (format!("{:#?}", stringify!($($data_default)*)));
-          )*
-          v.push (_w);
-        })+
-        v
-      }
-      fn events() -> Vec <&'static str> {
-        vec![$(stringify!($event)),*]
-      }
-      fn event_sources() -> Vec <&'static str> {
-        vec![$(stringify!($source)),*]
-      }
-      fn event_targets() -> Vec <&'static str> {
-        vec![$(stringify!($($target)*)),*]
-      }
-      fn event_actions() -> Vec <&'static str> {
-        vec![$(stringify!($($action)*)),*]
-      }
-    } // end impl MachineDotfile
+          $($($type_var : $type_constraint),+)*
+        ),+)*
+        {
+          fn name() -> &'static str {
+            stringify!($machine)
+          }
+          fn type_vars() -> Vec <String> {
+            let mut _v = Vec::new();
+            $($(
+            _v.push (format!(
+            "{} = {}", stringify!($type_var), std::any::type_name::<$type_var>()));
+            )+)*
+            _v
+          }
+          fn extended_state_names() -> Vec <&'static str> {
+            let mut _v = Vec::new();
+            $(
+            _v.push (stringify!($ext_name));
+            )*
+            _v
+          }
+          fn extended_state_types() -> Vec <&'static str> {
+            let mut _v = Vec::new();
+            $(
+            _v.push (stringify!($ext_type));
+            )*
+            _v
+          }
+          fn extended_state_defaults() -> Vec <&'static str> {
+            let mut _v = Vec::new();
+            $(
+            _v.push (stringify!($($ext_default)*));
+            )*
+            _v
+          }
+          fn self_reference() -> &'static str {
+            stringify!($($self_reference)*)
+          }
+          fn states() -> Vec <&'static str> {
+            vec![$(stringify!($state)),+]
+          }
+          fn state_initial() -> &'static str {
+            stringify!($initial)
+          }
+          fn state_terminal() -> &'static str {
+            stringify!($($terminal)*)
+          }
+          fn state_data_names() -> Vec <Vec <&'static str>> {
+            vec![$(vec![$(stringify!($data_name)),*]),+]
+          }
+          fn state_data_types() -> Vec <Vec <&'static str>> {
+            vec![$(vec![$(stringify!($data_type)),*]),+]
+          }
+          fn state_data_defaults() -> Vec <Vec <&'static str>> {
+            let mut v = Vec::new();
+            $({
+              let mut _w = Vec::new();
+              $(_w.push (stringify!($($data_default)*));)*
+              v.push (_w);
+            })+
+            v
+          }
+          /// This version does not evaluate expressions, only pretty prints them
+          fn state_data_pretty_defaults() -> Vec <Vec <String>> {
+            let mut v = Vec::new();
+            $({
+              let mut _w = Vec::new();
+              $(
+              _w.push (format!("{:#?}", stringify!($($data_default)*)));
+              )*
+              v.push (_w);
+            })+
+            v
+          }
+          fn events() -> Vec <&'static str> {
+            vec![$(stringify!($event)),*]
+          }
+          fn event_sources() -> Vec <&'static str> {
+            vec![$(stringify!($source)),*]
+          }
+          fn event_targets() -> Vec <&'static str> {
+            vec![$(stringify!($($target)*)),*]
+          }
+          fn event_actions() -> Vec <&'static str> {
+            vec![$(stringify!($($action)*)),*]
+          }
+          fn event_param_names() -> Vec <Vec <&'static str>> {
+            vec![$(vec![$(stringify!($param_name)),*]),*]
+          }
+          fn event_param_types() -> Vec <Vec <&'static str>> {
+            vec![$(vec![$(stringify!($param_type)),*]),*]
+          }
+          fn event_param_defaults() -> Vec <Vec <&'static str>> {
+            vec![$(vec![$(stringify!($($param_default)*)),*]),*]
+          }
+          fn state_entry_actions() -> Vec <&'static str> {
+            vec![$(stringify!($($($entry)*)*)),+]
+          }
+          fn state_exit_actions() -> Vec <&'static str> {
+            vec![$(stringify!($($($exit)*)*)),+]
+          }
+          fn initial_action() -> &'static str {
+            stringify!($($($initial_action)*)*)
+          }
+          fn terminate_success_action() -> &'static str {
+            stringify!($($($($terminate_success)*)*)*)
+          }
+          fn terminate_failure_action() -> &'static str {
+            stringify!($($($($terminate_failure)*)*)*)
+          }
+        } // end impl MachineDotfile
 
-    impl $(<$($type_var),+>)* ExtendedState $(<$($type_var),+>)* where
-    $($(
-      $($($type_var : $type_constraint),+)*
-    ),+)*
-    {
-      /// Creation method that allows overriding defaults. If a field does not
-      /// have a default specified it is a required argument.
-      // TODO: indicate which arguments are missing in case of failure
-      // TODO: make required arguments non-Option types?
-      pub fn new ($($ext_name : Option <$ext_type>),*) -> Option <Self> {
-        Some (Self {
-          $($ext_name: {
-            if let Some ($ext_name) = $ext_name {
-              $ext_name
-            } else {
-              $crate::def_machine!(@expr_option $($ext_default)*)?
-            }
-          }),*
-        })
+        impl $(<$($type_var),+>)* ExtendedState $(<$($type_var),+>)* where
+        $($(
+          $($($type_var : $type_constraint),+)*
+        ),+)*
+        {
+          /// Creation method that allows overriding defaults. If a field does not
+          /// have a default specified it is a required argument.
+          // TODO: indicate which arguments are missing in case of failure
+          // TODO: make required arguments non-Option types?
+          pub fn new ($($ext_name : Option <$ext_type>),*) -> Option <Self> {
+            Some (Self {
+              $($ext_name: {
+                if let Some ($ext_name) = $ext_name {
+                  $ext_name
+                } else {
+                  $crate::def_machine!(@expr_option $($ext_default)*)?
+                }
+              }),*
+            })
+          }
+        }
       }
     }
   };
@@ -1172,6 +1872,15 @@ macro_rules! def_machine_nodefault {
           $({ $($state_data:ident),* } => $action:block)*
         )*
       ]
+      $(logging: {
+        $(target: $log_target:literal)?
+        $(initialize: $log_initialize:ident)?
+        $(accepted: $log_accepted:ident)?
+        $(rejected: $log_rejected:ident)?
+        $(terminate_success: $log_terminate_success:ident)?
+        $(terminate_failure: $log_terminate_failure:ident)?
+      })?
+      $(fuzz: $($fuzz:ident),+)?
       initial_state: $initial:ident $({
         $(initial_action: $initial_action:block)*
       })*
@@ -1202,6 +1911,15 @@ macro_rules! def_machine_nodefault {
           $($($ext_name : $ext_type $(= $ext_default)*),*)*
         ]
         $(self_reference: $self_reference)*
+        $(logging: {
+          $(target: $log_target)?
+          $(initialize: $log_initialize)?
+          $(accepted: $log_accepted)?
+          $(rejected: $log_rejected)?
+          $(terminate_success: $log_terminate_success)?
+          $(terminate_failure: $log_terminate_failure)?
+        })?
+        $(fuzz: $($fuzz),+)?
         initial_state: $initial $({
           $(initial_action: $initial_action)*
         })*
@@ -1252,6 +1970,15 @@ macro_rules! def_machine_debug {
         $($ext_name:ident : $ext_type:ty $(= $ext_default:expr)*),*
       ]
       $(self_reference: $self_reference:ident)*
+      $(logging: {
+        $(target: $log_target:literal)?
+        $(initialize: $log_initialize:ident)?
+        $(accepted: $log_accepted:ident)?
+        $(rejected: $log_rejected:ident)?
+        $(terminate_success: $log_terminate_success:ident)?
+        $(terminate_failure: $log_terminate_failure:ident)?
+      })?
+      $(fuzz: $($fuzz:ident),+)?
       initial_state: $initial:ident $({
         $(initial_action: $initial_action:block)*
       })*
@@ -1263,223 +1990,340 @@ macro_rules! def_machine_debug {
 
   ) => {
 
-    $crate::def_machine_debug!{
-      @base
-      machine $machine
-        $(<$($type_var $(: { $($type_constraint),+ })*),+>)*
+    $crate::def_machine!{
+      @validate ($)
+      STATES [
+        $($state ($($data_name),*))+
+      ]
+      EVENTS [
+        $($event <$source> $(=> <$target>)*
+          { $($($state_data),*)* }
+        )*
+      ]
+      initial_state: $initial
+      $(terminal_state: $terminal)*
       {
-        STATES [
-          $(state $state ($($data_name : $data_type $(= $data_default)*),*)
-          $({
-            $(entry $entry)*
-            $(exit  $exit)*
-          })*)+
-        ]
-        EVENTS [
-          $(event $event <$source> $(=> <$target>)*
-            ($($param_name : $param_type $(= $param_default)*),*)
-            $({$($state_data),*} => $action)*
-          )*
-        ]
-        EXTENDED [
-          $($ext_name : $ext_type $(= $ext_default)*),*
-        ]
-        $(self_reference: $self_reference)*
-        initial_state: $initial $({
-          $(initial_action: $initial_action)*
-        })*
-        $(terminal_state: $terminal $({
-          $(terminate_success: $terminate_success)*
-          $(terminate_failure: $terminate_failure)*
-        })*)*
-      }
-    }
+        $crate::def_machine_debug!{
+          @base
+          machine $machine
+            $(<$($type_var $(: { $($type_constraint),+ })*),+>)*
+          {
+            STATES [
+              $(state $state ($($data_name : $data_type $(= $data_default)*),*)
+              $({
+                $(entry $entry)*
+                $(exit  $exit)*
+              })*)+
+            ]
+            EVENTS [
+              $(event $event <$source> $(=> <$target>)*
+                ($($param_name : $param_type $(= $param_default)*),*)
+                $({$($state_data),*} => $action)*
+              )*
+            ]
+            EXTENDED [
+              $($ext_name : $ext_type $(= $ext_default)*),*
+            ]
+            $(self_reference: $self_reference)*
+            $(logging: {
+              $(target: $log_target)?
+              $(initialize: $log_initialize)?
+              $(accepted: $log_accepted)?
+              $(rejected: $log_rejected)?
+              $(terminate_success: $log_terminate_success)?
+              $(terminate_failure: $log_terminate_failure)?
+            })?
+            $(fuzz: $($fuzz),+)?
+            initial_state: $initial $({
+              $(initial_action: $initial_action)*
+            })*
+            $(terminal_state: $terminal $({
+              $(terminate_success: $terminate_success)*
+              $(terminate_failure: $terminate_failure)*
+            })*)*
+          }
+        }
 
-    impl $(<$($type_var),+>)* $machine $(<$($type_var),+>)* where
-    $($(
-      $type_var : std::fmt::Debug,
-      $($($type_var : $type_constraint),+)*
-    ),+)*
-    {
-      pub fn initial() -> Self {
-        $crate::log::debug!(
-          machine=stringify!($machine), state=stringify!($initial);
-          "initialize state machine");
-        let mut extended_state = ExtendedState::initial();
-        let state = StateId::$initial.to_state (&mut extended_state);
-        let mut initial = Self { state, extended_state };
+        impl $(<$($type_var),+>)* $machine $(<$($type_var),+>)* where
+        $($(
+          $type_var : std::fmt::Debug,
+          $($($type_var : $type_constraint),+)*
+        ),+)*
         {
-          $(#[allow(unused_variables)]
-          let $self_reference = &mut initial;)*
-          $($($initial_action)*)*
+          pub fn initial() -> Self {
+            $crate::log::log!(
+              target: Self::LOG_CONFIG.target, Self::LOG_CONFIG.initialize,
+              machine=stringify!($machine), state=stringify!($initial);
+              "initialize state machine");
+            let mut extended_state = ExtendedState::initial();
+            let state = StateId::$initial.to_state (&mut extended_state);
+            let mut initial = Self {
+              state, extended_state, instance: None, metrics: None, coverage: None
+            };
+            {
+              $(#[allow(unused_variables)]
+              let $self_reference = &mut initial;)*
+              $($($initial_action)*)*
+            }
+            initial.state_entry();
+            initial
+          }
+
+          /// Run a new machine through the transition tour (see `paths::tour`)
+          /// using default event parameters and check the state after each event;
+          /// intended for use in tests
+          ///
+          /// # Panics
+          ///
+          /// Panics if an event is rejected or leads to an unexpected state.
+          // if no events are defined the event ID type is uninhabited and the loop
+          // body is unreachable
+          #[allow(dead_code, unreachable_code, unused_variables)]
+          pub fn assert_transition_tour() {
+            let mut machine = Self::initial();
+            for (step, (event_id, expected)) in
+              $crate::paths::tour::<Self>().into_iter().enumerate()
+            {
+              let state = machine.state_id();
+              if let Err (exception) = machine.handle_event (event_id.clone().into()) {
+                panic!("transition tour step {}: event {} rejected in state {}: {:?}",
+                  step, event_id.name(), state.name(), exception)
+              }
+              assert!(machine.state_id() == expected,
+                "transition tour step {}: event {} led from state {} to {} instead of {}",
+                step, event_id.name(), state.name(), machine.state_id().name(),
+                expected.name());
+            }
+          }
+
+          /// Run a new machine through a random walk of up to `steps` events with
+          /// default parameters chosen by a `SplitMix64` generator with the given
+          /// `seed`, checking the `invariant` after each event; intended for use in
+          /// tests. If the walk succeeds the machine is returned in the state the
+          /// walk left it in.
+          ///
+          /// # Panics
+          ///
+          /// Panics with the seed and event trace if an event is rejected, handling
+          /// an event panics or the invariant does not hold. The machine is leaked
+          /// instead of dropped so that its terminate actions can not panic while
+          /// the failure is reported.
+          #[allow(dead_code)]
+          pub fn assert_random_walk <I> (seed : u64, steps : usize, invariant : I)
+            -> Self
+          where
+            I : FnMut (&Self) -> Result <(), String>
+          {
+            let mut machine = Self::initial();
+            let mut rng = $crate::walk::SplitMix64::new (seed);
+            if let Err (failure) =
+              machine.random_walk (&mut rng, steps, |id, _| id.into(), invariant)
+            {
+              let _ = std::mem::ManuallyDrop::new (machine);
+              panic!("{}", failure)
+            }
+            machine
+          }
         }
-        initial.state_entry();
-        initial
-      }
-    }
 
-    impl $(<$($type_var),+>)* $crate::MachineDotfile
-      for $machine $(<$($type_var),+>)*
-    where
-    $($(
-      $type_var : std::fmt::Debug,
-      $($($type_var : $type_constraint),+)*
-    ),+)*
-    {
-      fn name() -> &'static str {
-        stringify!($machine)
-      }
-      fn type_vars() -> Vec <String> {
-        let mut _v = Vec::new();
+        impl $(<$($type_var),+>)* $crate::MachineDotfile
+          for $machine $(<$($type_var),+>)*
+        where
         $($(
-        _v.push (format!(
-          "{} = {}", stringify!($type_var), std::any::type_name::<$type_var>()));
-        )+)*
-        _v
-      }
-      fn extended_state_names() -> Vec <&'static str> {
-        let mut _v = Vec::new();
-        $(
-        _v.push (stringify!($ext_name));
-        )*
-        _v
-      }
-      fn extended_state_types() -> Vec <&'static str> {
-        let mut _v = Vec::new();
-        $(
-        _v.push (stringify!($ext_type));
-        )*
-        _v
-      }
-      fn extended_state_defaults() -> Vec <&'static str> {
-        let mut _v = Vec::new();
-        $(
-        _v.push ({
-          let default_expr = stringify!($($ext_default)*);
-          if !default_expr.is_empty() {
-            default_expr
-          } else {
-            concat!(stringify!($ext_type), "::default()")
+          $type_var : std::fmt::Debug,
+          $($($type_var : $type_constraint),+)*
+        ),+)*
+        {
+          fn name() -> &'static str {
+            stringify!($machine)
           }
-        });
-        )*
-        _v
-      }
-      fn self_reference() -> &'static str {
-        stringify!($($self_reference)*)
-      }
-      fn states() -> Vec <&'static str> {
-        vec![$(stringify!($state)),+]
-      }
-      fn state_initial() -> &'static str {
-        stringify!($initial)
-      }
-      fn state_terminal() -> &'static str {
-        stringify!($($terminal)*)
-      }
-      fn state_data_names() -> Vec <Vec <&'static str>> {
-        let mut v = Vec::new();
-        $({
-          let mut _w = Vec::new();
-          $(_w.push (stringify!($data_name));)*
-          v.push (_w);
-        })+
-        v
-      }
-      fn state_data_types() -> Vec <Vec <&'static str>> {
-        let mut v = Vec::new();
-        $({
-          let mut _w = Vec::new();
-          $(_w.push (stringify!($data_type));)*
-          v.push (_w);
-        })+
-        v
-      }
-      fn state_data_defaults() -> Vec <Vec <&'static str>> {
-        let mut v = Vec::new();
-        $({
-          let mut _w = Vec::new();
-          $(
-          _w.push ({
-            let default_expr = stringify!($($data_default)*);
-            if !default_expr.is_empty() {
-              default_expr
-            } else {
-              concat!(stringify!($data_type), "::default()")
+          fn type_vars() -> Vec <String> {
+            let mut _v = Vec::new();
+            $($(
+            _v.push (format!(
+              "{} = {}", stringify!($type_var), std::any::type_name::<$type_var>()));
+            )+)*
+            _v
+          }
+          fn extended_state_names() -> Vec <&'static str> {
+            let mut _v = Vec::new();
+            $(
+            _v.push (stringify!($ext_name));
+            )*
+            _v
+          }
+          fn extended_state_types() -> Vec <&'static str> {
+            let mut _v = Vec::new();
+            $(
+            _v.push (stringify!($ext_type));
+            )*
+            _v
+          }
+          fn extended_state_defaults() -> Vec <&'static str> {
+            let mut _v = Vec::new();
+            $(
+            _v.push ({
+              let default_expr = stringify!($($ext_default)*);
+              if !default_expr.is_empty() {
+                default_expr
+              } else {
+                concat!(stringify!($ext_type), "::default()")
+              }
+            });
+            )*
+            _v
+          }
+          fn self_reference() -> &'static str {
+            stringify!($($self_reference)*)
+          }
+          fn states() -> Vec <&'static str> {
+            vec![$(stringify!($state)),+]
+          }
+          fn state_initial() -> &'static str {
+            stringify!($initial)
+          }
+          fn state_terminal() -> &'static str {
+            stringify!($($terminal)*)
+          }
+          fn state_data_names() -> Vec <Vec <&'static str>> {
+            vec![$(vec![$(stringify!($data_name)),*]),+]
+          }
+          fn state_data_types() -> Vec <Vec <&'static str>> {
+            vec![$(vec![$(stringify!($data_type)),*]),+]
+          }
+          fn state_data_defaults() -> Vec <Vec <&'static str>> {
+            let mut v = Vec::new();
+            $({
+              let mut _w = Vec::new();
+              $(
+              _w.push ({
+                let default_expr = stringify!($($data_default)*);
+                if !default_expr.is_empty() {
+                  default_expr
+                } else {
+                  concat!(stringify!($data_type), "::default()")
+                }
+              });
+              )*
+              v.push (_w);
+            })+
+            v
+          }
+          /// &#9888; This function creates default values for each state data field
+          /// and creates a pretty printed string of the value
+          fn state_data_pretty_defaults() -> Vec <Vec <String>> {
+            let mut v = Vec::new();
+            $({
+              let mut _w = Vec::new();
+              $(
+              let default_val : $data_type
+                = $crate::def_machine_debug!(@expr_default $($data_default)*);
+              _w.push (format!("{:#?}", default_val));
+              )*
+              v.push (_w);
+            })+
+            v
+          }
+          fn events() -> Vec <&'static str> {
+            vec![$(stringify!($event)),*]
+          }
+          fn event_sources() -> Vec <&'static str> {
+            vec![$(stringify!($source)),*]
+          }
+          fn event_targets() -> Vec <&'static str> {
+            vec![$(stringify!($($target)*)),*]
+          }
+          fn event_actions() -> Vec <&'static str> {
+            vec![$(stringify!($($action)*)),*]
+          }
+          fn event_param_names() -> Vec <Vec <&'static str>> {
+            vec![$(vec![$(stringify!($param_name)),*]),*]
+          }
+          fn event_param_types() -> Vec <Vec <&'static str>> {
+            vec![$(vec![$(stringify!($param_type)),*]),*]
+          }
+          fn event_param_defaults() -> Vec <Vec <&'static str>> {
+            vec![$(vec![$(stringify!($($param_default)*)),*]),*]
+          }
+          fn state_entry_actions() -> Vec <&'static str> {
+            vec![$(stringify!($($($entry)*)*)),+]
+          }
+          fn state_exit_actions() -> Vec <&'static str> {
+            vec![$(stringify!($($($exit)*)*)),+]
+          }
+          fn initial_action() -> &'static str {
+            stringify!($($($initial_action)*)*)
+          }
+          fn terminate_success_action() -> &'static str {
+            stringify!($($($($terminate_success)*)*)*)
+          }
+          fn terminate_failure_action() -> &'static str {
+            stringify!($($($($terminate_failure)*)*)*)
+          }
+        } // end impl MachineDotfile
+
+        impl $(<$($type_var),+>)* ExtendedState $(<$($type_var),+>)* where
+        $($(
+          $type_var : std::fmt::Debug,
+          $($($type_var : $type_constraint),+)*
+        ),+)*
+        {
+          pub fn initial() -> Self {
+            Self {
+              $($ext_name: $crate::def_machine_debug!(@expr_default $($ext_default)*)),*
             }
-          });
-          )*
-          v.push (_w);
-        })+
-        v
-      }
-      /// &#9888; This function creates default values for each state data field
-      /// and creates a pretty printed string of the value
-      fn state_data_pretty_defaults() -> Vec <Vec <String>> {
-        let mut v = Vec::new();
-        $({
-          let mut _w = Vec::new();
-          $(
-          let default_val : $data_type
-            = $crate::def_machine_debug!(@expr_default $($data_default)*);
-          _w.push (format!("{:#?}", default_val));
-          )*
-          v.push (_w);
-        })+
-        v
-      }
-      fn events() -> Vec <&'static str> {
-        vec![$(stringify!($event)),*]
-      }
-      fn event_sources() -> Vec <&'static str> {
-        vec![$(stringify!($source)),*]
-      }
-      fn event_targets() -> Vec <&'static str> {
-        vec![$(stringify!($($target)*)),*]
-      }
-      fn event_actions() -> Vec <&'static str> {
-        vec![$(stringify!($($action)*)),*]
-      }
-    } // end impl MachineDotfile
+          }
 
-    impl $(<$($type_var),+>)* ExtendedState $(<$($type_var),+>)* where
-    $($(
-      $type_var : std::fmt::Debug,
-      $($($type_var : $type_constraint),+)*
-    ),+)*
-    {
-      pub fn initial() -> Self {
-        Self {
-          $($ext_name: $crate::def_machine_debug!(@expr_default $($ext_default)*)),*
+          /// Creation method that allows overriding defaults.
+          pub fn new ($($ext_name : Option <$ext_type>),*) -> Self {
+            Self {
+              $($ext_name: $ext_name.unwrap_or (
+                $crate::def_machine_debug!(@expr_default $($ext_default)*))
+              ),*
+            }
+          }
         }
-      }
 
-      /// Creation method that allows overriding defaults.
-      pub fn new ($($ext_name : Option <$ext_type>),*) -> Self {
-        Self {
-          $($ext_name: $ext_name.unwrap_or (
-            $crate::def_machine_debug!(@expr_default $($ext_default)*))
-          ),*
+        impl <'event> Event <'event> {
+          /// Shortest sequence of events with default parameters leading from
+          /// state `from` to state `to`, or `None` if `to` can not be reached
+          #[allow(dead_code)]
+          pub fn path (from : &StateId, to : &StateId) -> Option <Vec <Self>> {
+            $crate::paths::shortest_in (StateId::ALL, EventId::TRANSITIONS,
+              from, to
+            ).map (|path| path.into_iter().map (Self::from).collect())
+          }
+
+          /// Shortest sequences of events with default parameters leading from the
+          /// initial state to each reachable state
+          #[allow(dead_code)]
+          pub fn paths() -> Vec <(StateId, Vec <Self>)> {
+            $crate::paths::from_state_in (StateId::ALL, EventId::TRANSITIONS,
+              &StateId::initial()
+            ).into_iter().map (|(state, path)|
+              (state, path.into_iter().map (Self::from).collect())
+            ).collect()
+          }
         }
-      }
-    }
 
-    impl <'event> From <EventId> for Event <'event> {
-      /// Construct an event with default parameters for the given ID
-      fn from (id : EventId) -> Self {
-        let _params = id.clone().into();
-        Event { id, params: _params }
-      }
-    }
+        impl <'event> From <EventId> for Event <'event> {
+          /// Construct an event with default parameters for the given ID
+          fn from (id : EventId) -> Self {
+            let _params = id.clone().into();
+            Event { id, params: _params }
+          }
+        }
 
-    impl <'event> From <EventId> for EventParams <'event> {
-      fn from (id : EventId) -> Self {
-        match id {
-          $(EventId::$event => EventParams::$event {
-            $($param_name:
-              $crate::def_machine_debug!(@expr_default $($param_default)*)
-            ),*
-          }),*
+        impl <'event> From <EventId> for EventParams <'event> {
+          fn from (id : EventId) -> Self {
+            match id {
+              $(EventId::$event => EventParams::$event {
+                $($param_name:
+                  $crate::def_machine_debug!(@expr_default $($param_default)*)
+                ),*
+              }),*
+            }
+          }
         }
       }
     }
@@ -1513,6 +2357,15 @@ macro_rules! def_machine_debug {
           $({ $($state_data:ident),* } => $action:block)*
         )*
       ]
+      $(logging: {
+        $(target: $log_target:literal)?
+        $(initialize: $log_initialize:ident)?
+        $(accepted: $log_accepted:ident)?
+        $(rejected: $log_rejected:ident)?
+        $(terminate_success: $log_terminate_success:ident)?
+        $(terminate_failure: $log_terminate_failure:ident)?
+      })?
+      $(fuzz: $($fuzz:ident),+)?
       initial_state: $initial:ident $({
         $(initial_action: $initial_action:block)*
       })*
@@ -1543,6 +2396,15 @@ macro_rules! def_machine_debug {
           $($($ext_name : $ext_type $(= $ext_default)*),*)*
         ]
         $(self_reference: $self_reference)*
+        $(logging: {
+          $(target: $log_target)?
+          $(initialize: $log_initialize)?
+          $(accepted: $log_accepted)?
+          $(rejected: $log_rejected)?
+          $(terminate_success: $log_terminate_success)?
+          $(terminate_failure: $log_terminate_failure)?
+        })?
+        $(fuzz: $($fuzz),+)?
         initial_state: $initial $({
           $(initial_action: $initial_action)*
         })*
@@ -1582,14 +2444,28 @@ macro_rules! def_machine_debug {
       #[allow(unreachable_code)]
       match _event.transition() {
         Transition::Universal (target_id) => {
-          $crate::log::debug!(
-            machine=stringify!($machine), state:?=self.state.id,
+          $crate::log::log!(
+            target: Self::LOG_CONFIG.target, Self::LOG_CONFIG.accepted,
+            machine=stringify!($machine), instance=self.instance_name(),
+            state:?=self.state.id,
             event:?=_event.id, transition="Universal", target:?=target_id;
             "handle state machine event");
+          $crate::log::trace!(
+            target: Self::LOG_CONFIG.target,
+            machine=stringify!($machine), instance=self.instance_name(),
+            params:?=_event.params, data:?=self.state.data;
+            "handle state machine event parameters");
+          if let Some (metrics) = self.metrics.as_mut() {
+            metrics.event_accepted (_event.id.clone() as usize);
+          }
+          if let Some (coverage) = self.coverage.as_mut() {
+            coverage.event_handled (
+              self.state.id.clone() as usize, _event.id.clone() as usize);
+          }
           self.state_exit();
           { // event action
             // bring extended state variables into scope
-            #[allow(unused_variables)]
+            #[allow(unused_mut, unused_variables)]
             match &mut self.extended_state {
               &mut ExtendedState { $(ref mut $ext_name,)*.. } => {
                 // map each event to an action
@@ -1609,16 +2485,35 @@ macro_rules! def_machine_debug {
           let state  = target_id.to_state (&mut self.extended_state);
           self.state = state;
           self.state_entry();
+          $crate::log::trace!(
+            target: Self::LOG_CONFIG.target,
+            machine=stringify!($machine), instance=self.instance_name(),
+            state:?=self.state.id, data:?=self.state.data;
+            "handle state machine event result");
           Ok (())
         }
         Transition::Internal (source_id) => {
           if self.state.id == source_id {
-            $crate::log::debug!(
-              machine=stringify!($machine), state:?=self.state.id,
+            $crate::log::log!(
+              target: Self::LOG_CONFIG.target, Self::LOG_CONFIG.accepted,
+              machine=stringify!($machine), instance=self.instance_name(),
+              state:?=self.state.id,
               event:?=_event.id, transition="Internal", source:?=source_id;
               "handle state machine event");
+            $crate::log::trace!(
+              target: Self::LOG_CONFIG.target,
+              machine=stringify!($machine), instance=self.instance_name(),
+              params:?=_event.params, data:?=self.state.data;
+              "handle state machine event parameters");
+            if let Some (metrics) = self.metrics.as_mut() {
+              metrics.event_accepted (_event.id.clone() as usize);
+            }
+            if let Some (coverage) = self.coverage.as_mut() {
+              coverage.event_handled (
+                self.state.id.clone() as usize, _event.id.clone() as usize);
+            }
             // bring extended state variables into scope
-            #[allow(unused_variables)]
+            #[allow(unused_mut, unused_variables)]
             match &mut self.extended_state {
               &mut ExtendedState { $(ref mut $ext_name,)*.. } => {
                 // map each event to an action
@@ -1647,28 +2542,52 @@ macro_rules! def_machine_debug {
                 }
               }
             }
+            $crate::log::trace!(
+              target: Self::LOG_CONFIG.target,
+              machine=stringify!($machine), instance=self.instance_name(),
+              state:?=self.state.id, data:?=self.state.data;
+              "handle state machine event result");
             Ok (())
           } else {
             let exception = $crate::HandleEventException::WrongState;
-            $crate::log::warn!(
-              machine=stringify!($machine), state:?=self.state.id,
+            $crate::log::log!(
+              target: Self::LOG_CONFIG.target, Self::LOG_CONFIG.rejected,
+              machine=stringify!($machine), instance=self.instance_name(),
+              state:?=self.state.id,
               event:?=_event.id, transition="Internal", source:?=source_id,
               exception:?;
               "handle state machine event exception");
+            if let Some (metrics) = self.metrics.as_mut() {
+              metrics.event_rejected (_event.id.clone() as usize);
+            }
             Err (exception)
           }
         }
         Transition::External (source_id, target_id) => {
           if self.state.id == source_id {
-            $crate::log::debug!(
-              machine=stringify!($machine), state:?=self.state.id,
+            $crate::log::log!(
+              target: Self::LOG_CONFIG.target, Self::LOG_CONFIG.accepted,
+              machine=stringify!($machine), instance=self.instance_name(),
+              state:?=self.state.id,
               event:?=_event.id, transition="External", source:?=source_id,
               target:?=target_id;
               "handle state machine event");
+            $crate::log::trace!(
+              target: Self::LOG_CONFIG.target,
+              machine=stringify!($machine), instance=self.instance_name(),
+              params:?=_event.params, data:?=self.state.data;
+              "handle state machine event parameters");
+            if let Some (metrics) = self.metrics.as_mut() {
+              metrics.event_accepted (_event.id.clone() as usize);
+            }
+            if let Some (coverage) = self.coverage.as_mut() {
+              coverage.event_handled (
+                self.state.id.clone() as usize, _event.id.clone() as usize);
+            }
             self.state_exit();
             { // event action
               // bring extended state variables into scope
-              #[allow(unused_variables)]
+              #[allow(unused_mut, unused_variables)]
               match &mut self.extended_state {
                 &mut ExtendedState { $(ref mut $ext_name,)*.. } => {
                   // map each event to an action
@@ -1688,14 +2607,24 @@ macro_rules! def_machine_debug {
             let state  = target_id.to_state (&mut self.extended_state);
             self.state = state;
             self.state_entry();
+            $crate::log::trace!(
+              target: Self::LOG_CONFIG.target,
+              machine=stringify!($machine), instance=self.instance_name(),
+              state:?=self.state.id, data:?=self.state.data;
+              "handle state machine event result");
             Ok (())
           } else {
             let exception = $crate::HandleEventException::WrongState;
-            $crate::log::warn!(
-              machine=stringify!($machine), state:?=self.state.id,
+            $crate::log::log!(
+              target: Self::LOG_CONFIG.target, Self::LOG_CONFIG.rejected,
+              machine=stringify!($machine), instance=self.instance_name(),
+              state:?=self.state.id,
               event:?=_event.id, transition="External", source:?=source_id,
               target:?=target_id, exception:?;
               "handle state machine event exception");
+            if let Some (metrics) = self.metrics.as_mut() {
+              metrics.event_rejected (_event.id.clone() as usize);
+            }
             Err ($crate::HandleEventException::WrongState)
           }
         }
@@ -1798,6 +2727,27 @@ macro_rules! def_machine_debug {
     Transition::Universal (StateId::$target)
   };
 
+  //
+  //  @transition_row: external
+  //
+  ( @transition_row $event:ident <$source:ident> => <$target:ident> ) => {
+    (EventId::$event, Some (StateId::$source), Some (StateId::$target))
+  };
+
+  //
+  //  @transition_row: internal
+  //
+  ( @transition_row $event:ident <$source:ident> ) => {
+    (EventId::$event, Some (StateId::$source), None)
+  };
+
+  //
+  //  @transition_row: universal
+  //
+  ( @transition_row $event:ident <*> => <$target:ident> ) => {
+    (EventId::$event, None, Some (StateId::$target))
+  };
+
   //
   //  @expr_default: override default
   //
@@ -1818,6 +2768,26 @@ macro_rules! def_machine_debug {
   //
   ( @expr_option ) => { None };
 
+  //
+  //  @log_target: default
+  //
+  ( @log_target ) => { module_path!() };
+
+  //
+  //  @log_target: override default
+  //
+  ( @log_target $target:literal ) => { $target };
+
+  //
+  //  @log_level: default
+  //
+  ( @log_level $default:ident ) => { $crate::log::Level::$default };
+
+  //
+  //  @log_level: override default
+  //
+  ( @log_level $default:ident $level:ident ) => { $crate::log::Level::$level };
+
   //
   //  @base implementation rule
   //
@@ -1845,6 +2815,15 @@ macro_rules! def_machine_debug {
         $($ext_name:ident : $ext_type:ty $(= $ext_default:expr)*),*
       ]
       $(self_reference: $self_reference:ident)*
+      $(logging: {
+        $(target: $log_target:literal)?
+        $(initialize: $log_initialize:ident)?
+        $(accepted: $log_accepted:ident)?
+        $(rejected: $log_rejected:ident)?
+        $(terminate_success: $log_terminate_success:ident)?
+        $(terminate_failure: $log_terminate_failure:ident)?
+      })?
+      $(fuzz: $($fuzz:ident),+)?
       initial_state: $initial:ident $({
         $(initial_action: $initial_action:block)*
       })*
@@ -1856,6 +2835,20 @@ macro_rules! def_machine_debug {
 
   ) => {
 
+    $($($crate::def_machine!(@fuzz_target $fuzz);)+)?
+    $crate::def_machine_arbitrary!{
+      [$($($fuzz)+)?]
+      EVENTS [
+        $($event ($($param_name : $param_type),*))*
+      ]
+    }
+    $crate::def_machine_proptest!{
+      [$($($fuzz)+)?]
+      EVENTS [
+        $($event ($($param_name : $param_type),*))*
+      ]
+    }
+
     #[derive(Debug)]
     pub struct $machine $(<$($type_var),+>)* where
     $($(
@@ -1864,7 +2857,10 @@ macro_rules! def_machine_debug {
     ),+)*
     {
       state          : State,
-      extended_state : ExtendedState $(<$($type_var),+>)*
+      extended_state : ExtendedState $(<$($type_var),+>)*,
+      instance       : Option <String>,
+      metrics        : Option <Box <$crate::metrics::Collector>>,
+      coverage       : Option <Box <$crate::coverage::Tracker>>
     }
 
     #[derive(Debug)]
@@ -1927,6 +2923,21 @@ macro_rules! def_machine_debug {
       $($($type_var : $type_constraint),+)*
     ),+)*
     {
+      /// Log target and levels for this state machine
+      pub const LOG_CONFIG : $crate::LogConfig = $crate::LogConfig {
+        target:            $crate::def_machine_debug!(@log_target $($($log_target)?)?),
+        initialize:        $crate::def_machine_debug!(
+          @log_level Debug $($($log_initialize)?)?),
+        accepted:          $crate::def_machine_debug!(
+          @log_level Debug $($($log_accepted)?)?),
+        rejected:          $crate::def_machine_debug!(
+          @log_level Warn $($($log_rejected)?)?),
+        terminate_success: $crate::def_machine_debug!(
+          @log_level Debug $($($log_terminate_success)?)?),
+        terminate_failure: $crate::def_machine_debug!(
+          @log_level Warn $($($log_terminate_failure)?)?)
+      };
+
       pub fn report_sizes() where $($($type_var : 'static),+)* {
         let machine_name = stringify!($machine);
         let machine_type = std::any::type_name::<Self>();
@@ -1935,50 +2946,221 @@ macro_rules! def_machine_debug {
         println!("...{} report sizes", machine_name);
       }
 
-      pub fn new (mut extended_state : ExtendedState $(<$($type_var),+>)*)
-        -> Self
+      pub fn new (mut extended_state : ExtendedState $(<$($type_var),+>)*)
+        -> Self
+      {
+        let state   = StateId::$initial.to_state (&mut extended_state);
+        let mut new = Self {
+          state, extended_state, instance: None, metrics: None, coverage: None
+        };
+        {
+          $(#[allow(unused_variables)]
+          let $self_reference = &mut new;)*
+          $($($initial_action)*)*
+        }
+        new.state_entry();
+        new
+      }
+
+      #[allow(dead_code)]
+      #[inline]
+      pub fn state (&self) -> &State {
+        &self.state
+      }
+
+      #[allow(dead_code)]
+      #[inline]
+      pub fn state_id (&self) -> StateId {
+        self.state().id().clone()
+      }
+
+      #[allow(dead_code)]
+      #[inline]
+      pub fn state_data (&self) -> &StateData {
+        self.state().data()
+      }
+
+      #[allow(dead_code)]
+      #[inline]
+      pub fn extended_state (&self) -> &ExtendedState $(<$($type_var),+>)* {
+        &self.extended_state
+      }
+
+      #[allow(dead_code)]
+      #[inline]
+      pub fn extended_state_mut (&mut self)
+        -> &mut ExtendedState $(<$($type_var),+>)*
+      {
+        &mut self.extended_state
+      }
+
+      /// Name or ID of this instance included in log records
+      #[allow(dead_code)]
+      #[inline]
+      pub fn instance_name (&self) -> Option <&str> {
+        self.instance.as_deref()
+      }
+
+      /// Set a name or ID of this instance to be included in subsequent log
+      /// records
+      #[allow(dead_code)]
+      #[inline]
+      pub fn set_instance_name (&mut self, name : String) {
+        self.instance = Some (name);
+      }
+
+      /// Whether the given event would be accepted in the current state
+      #[allow(dead_code)]
+      pub fn can_handle (&self, event_id : &EventId) -> bool {
+        match event_id.transition() {
+          Transition::Universal (_) => true,
+          Transition::Internal (source_id) |
+          Transition::External (source_id, _) => self.state.id == source_id
+        }
+      }
+
+      /// Describe the state exit, event and state entry actions that would be
+      /// performed and the resulting state if the given event were handled in
+      /// the current state, without modifying the machine or running any
+      /// actions
+      // if no events are defined the event ID type is uninhabited and the body
+      // is unreachable
+      #[allow(dead_code, unreachable_code, unused_variables)]
+      pub fn preview (&self, event : &Event) -> Result <
+        $crate::TransitionPlan <StateId, EventId>, $crate::HandleEventException
+      > {
+        let event_action = Some (event.id.clone())
+          .filter (EventId::has_action);
+        let (exit_action, entry_action, target) = match event.transition() {
+          Transition::Universal (target_id) => (
+            Some (self.state.id.clone()).filter (StateId::has_exit_action),
+            Some (target_id.clone()).filter (StateId::has_entry_action),
+            target_id
+          ),
+          Transition::Internal (source_id) if self.state.id == source_id =>
+            (None, None, source_id),
+          Transition::External (source_id, target_id)
+            if self.state.id == source_id
+          => (
+            Some (source_id).filter (StateId::has_exit_action),
+            Some (target_id.clone()).filter (StateId::has_entry_action),
+            target_id
+          ),
+          _ => return Err ($crate::HandleEventException::WrongState)
+        };
+        Ok ($crate::TransitionPlan {
+          event: event.id.clone(), exit_action, event_action, entry_action, target
+        })
+      }
+
+      /// Events that would be accepted in the current state, in order of
+      /// definition
+      #[allow(dead_code)]
+      pub fn enabled_events (&self) -> impl Iterator <Item=EventId> + '_ {
+        EventId::ALL.iter().filter (|id| self.can_handle (id)).cloned()
+      }
+
+      /// Generate a DOT file for the machine with the current state
+      /// highlighted, the `Debug` values of the extended state and the current
+      /// state data, and the enabled events marked
+      #[allow(dead_code)]
+      pub fn dotfile_instance (&self) -> String {
+        let extended_state = vec![
+          $(format!("{:?}", self.extended_state.$ext_name)),*
+        ];
+        let state_data = match self.state_data() {
+          $(StateData::$state { $($data_name),* } =>
+            vec![$(format!("{:?}", $data_name)),*]),+
+        };
+        let enabled : Vec <bool> = EventId::ALL.iter()
+          .map (|id| self.can_handle (id)).collect();
+        $crate::instance_dotfile::<Self> (self.state_id().name(),
+          Some (&extended_state), Some (&state_data), &enabled)
+      }
+
+      /// Handle up to `steps` events chosen at random among the enabled events,
+      /// with parameters created by `event`, checking the `invariant` after each
+      /// step (see `walk::run`)
+      // if no events are defined the event ID type is uninhabited and the
+      // event handler is unreachable
+      #[allow(dead_code, unreachable_code, unused_variables)]
+      pub fn random_walk <'event, R, F, I> (&mut self,
+        rng       : &mut R,
+        steps     : usize,
+        mut event : F,
+        invariant : I
+      ) -> Result <(), $crate::walk::Failure <EventId>> where
+        R : $crate::walk::Rng,
+        F : FnMut (EventId, &mut R) -> Event <'event>,
+        I : FnMut (&Self) -> Result <(), String>
       {
-        let state   = StateId::$initial.to_state (&mut extended_state);
-        let mut new = Self { state, extended_state };
-        {
-          $(#[allow(unused_variables)]
-          let $self_reference = &mut new;)*
-          $($($initial_action)*)*
-        }
-        new.state_entry();
-        new
+        $crate::walk::run (self, rng, steps,
+          |machine| machine.enabled_events().collect(),
+          |machine, id, rng| machine.handle_event (event (id.clone(), rng)),
+          invariant)
       }
 
+      /// Start collecting transition metrics for this instance, replacing any
+      /// previously collected metrics.
+      ///
+      /// The current state is counted as entered when collection starts.
       #[allow(dead_code)]
-      #[inline]
-      pub fn state (&self) -> &State {
-        &self.state
+      pub fn enable_metrics (&mut self,
+        clock : Box <dyn $crate::metrics::Clock + Send + Sync>
+      ) {
+        let mut metrics = $crate::metrics::Collector::new (
+          clock, StateId::VARIANT_COUNT, EventId::VARIANT_COUNT);
+        metrics.state_entered (self.state.id.clone() as usize);
+        self.metrics = Some (Box::new (metrics));
       }
 
+      /// Stop collecting transition metrics and discard collected metrics
       #[allow(dead_code)]
-      #[inline]
-      pub fn state_id (&self) -> StateId {
-        self.state().id().clone()
+      pub fn disable_metrics (&mut self) {
+        self.metrics = None;
       }
 
+      /// Snapshot of collected transition metrics, or `None` if metrics
+      /// collection is not enabled
       #[allow(dead_code)]
-      #[inline]
-      pub fn state_data (&self) -> &StateData {
-        self.state().data()
+      pub fn metrics (&self) -> Option <$crate::metrics::Snapshot> {
+        use $crate::MachineDotfile;
+        self.metrics.as_ref().map (|metrics| metrics.snapshot (
+          Self::name(), self.instance_name(), &Self::states(), &Self::events()))
       }
 
+      /// Start tracking transition coverage for this instance, replacing any
+      /// previously tracked coverage
       #[allow(dead_code)]
-      #[inline]
-      pub fn extended_state (&self) -> &ExtendedState $(<$($type_var),+>)* {
-        &self.extended_state
+      pub fn enable_coverage (&mut self) {
+        self.coverage = Some (Box::new ($crate::coverage::Tracker::new (
+          StateId::VARIANT_COUNT, EventId::VARIANT_COUNT)));
       }
 
+      /// Stop tracking transition coverage and discard tracked coverage
       #[allow(dead_code)]
-      #[inline]
-      pub fn extended_state_mut (&mut self)
-        -> &mut ExtendedState $(<$($type_var),+>)*
-      {
-        &mut self.extended_state
+      pub fn disable_coverage (&mut self) {
+        self.coverage = None;
+      }
+
+      /// Report of tracked transition coverage, or `None` if coverage tracking
+      /// is not enabled
+      #[allow(dead_code)]
+      pub fn coverage (&self) -> Option <$crate::coverage::Report> {
+        use $crate::MachineDotfile;
+        self.coverage.as_ref().map (|coverage| {
+          let transitions : Vec <_> = EventId::TRANSITIONS.iter()
+            .map (|(event, source, target)| (
+              event.name(),
+              source.as_ref().map (StateId::name),
+              target.as_ref().map (StateId::name)
+            )).collect();
+          let states : Vec <_> = StateId::ALL.iter()
+            .map (|state|
+              (state.name(), state.has_entry_action(), state.has_exit_action()))
+            .collect();
+          coverage.report (Self::name(), &transitions, &states)
+        })
       }
 
       $crate::def_machine_debug!{
@@ -1998,6 +3180,12 @@ macro_rules! def_machine_debug {
       }
 
       fn state_entry (&mut self) {
+        if let Some (metrics) = self.metrics.as_mut() {
+          metrics.state_entered (self.state.id.clone() as usize);
+        }
+        if let Some (coverage) = self.coverage.as_mut() {
+          coverage.state_entered (self.state.id.clone() as usize);
+        }
         // bring extended state variables into scope
         #[allow(unused_variables)]
         match &mut self.extended_state {
@@ -2012,6 +3200,12 @@ macro_rules! def_machine_debug {
         }
       }
       fn state_exit (&mut self) {
+        if let Some (metrics) = self.metrics.as_mut() {
+          metrics.state_exited();
+        }
+        if let Some (coverage) = self.coverage.as_mut() {
+          coverage.state_exited (self.state.id.clone() as usize);
+        }
         // bring extended state variables into scope
         #[allow(unused_variables)]
         match &mut self.extended_state {
@@ -2065,25 +3259,63 @@ macro_rules! def_machine_debug {
       fn drop (&mut self) {
         self.state_exit();
         let _state_id = self.state.id.clone();
+        $(
+        if _state_id != StateId::$terminal {
+          $crate::log::log!(
+            target: Self::LOG_CONFIG.target, Self::LOG_CONFIG.terminate_failure,
+            machine=stringify!($machine), instance=self.instance_name(),
+            state:?=_state_id, terminal:?=StateId::$terminal;
+            "terminate state machine failure: not in terminal state");
+        } else {
+          $crate::log::log!(
+            target: Self::LOG_CONFIG.target, Self::LOG_CONFIG.terminate_success,
+            machine=stringify!($machine), instance=self.instance_name(),
+            state:?=_state_id;
+            "terminate state machine success");
+        }
+        )*
+        // log records borrow the instance name so the self reference is
+        // introduced after logging
         $(#[allow(unused_variables)]
         let $self_reference = &mut *self;)*
         $(
         if _state_id != StateId::$terminal {
-          $crate::log::warn!(
-            machine=stringify!($machine), state:?=_state_id,
-            terminal:?=StateId::$terminal;
-            "terminate state machine failure: not in terminal state");
           $($($terminate_failure)*)*
         } else {
-          $crate::log::debug!(
-            machine=stringify!($machine), state:?=_state_id;
-            "terminate state machine success");
           $($($terminate_success)*)*
         }
         )*
       }
     }
 
+    impl $(<$($type_var),+>)* $crate::MachineMetadata
+      for $machine $(<$($type_var),+>)*
+    where
+    $($(
+      $type_var : std::fmt::Debug,
+      $($($type_var : $type_constraint),+)*
+    ),+)*
+    {
+      type StateId = StateId;
+      type EventId = EventId;
+      const STATES : &'static [StateId] = StateId::ALL;
+      const EVENTS : &'static [EventId] = EventId::ALL;
+      const TRANSITIONS
+        : &'static [(EventId, Option <StateId>, Option <StateId>)]
+        = EventId::TRANSITIONS;
+      const STATE_INITIAL  : StateId = StateId::$initial;
+      const STATE_TERMINAL : Option <StateId> =
+        $crate::def_machine_debug!(@expr_option $(StateId::$terminal)*);
+      #[inline]
+      fn state_name (id : &StateId) -> &'static str {
+        id.name()
+      }
+      #[inline]
+      fn event_name (id : &EventId) -> &'static str {
+        id.name()
+      }
+    }
+
     impl State {
       #[inline]
       pub fn id (&self) -> &StateId {
@@ -2097,6 +3329,30 @@ macro_rules! def_machine_debug {
     }
 
     impl StateId {
+      /// All state IDs in order of definition
+      pub const ALL : &'static [StateId] = &[$(StateId::$state),+];
+
+      #[inline]
+      pub const fn name (&self) -> &'static str {
+        match *self {
+          $(StateId::$state => stringify!($state)),+
+        }
+      }
+
+      /// Whether the state has an entry action
+      pub const fn has_entry_action (&self) -> bool {
+        match *self {
+          $(StateId::$state => $crate::is_action (stringify!($($($entry)*)*))),+
+        }
+      }
+
+      /// Whether the state has an exit action
+      pub const fn has_exit_action (&self) -> bool {
+        match *self {
+          $(StateId::$state => $crate::is_action (stringify!($($($exit)*)*))),+
+        }
+      }
+
       #[inline]
       pub const fn initial() -> Self {
         StateId::$initial
@@ -2138,6 +3394,33 @@ macro_rules! def_machine_debug {
     }
 
     impl EventId {
+      /// All event IDs in order of definition
+      pub const ALL : &'static [EventId] = &[$(EventId::$event),*];
+
+      /// Event, source and target state of each transition in order of
+      /// definition; see `MachineMetadata::TRANSITIONS`
+      pub const TRANSITIONS
+        : &'static [(EventId, Option <StateId>, Option <StateId>)]
+        = &[$(
+          $crate::def_machine_debug!(@transition_row $event <$source> $(=> <$target>)*)
+        ),*];
+
+      #[inline]
+      #[allow(clippy::uninhabited_references)]
+      pub const fn name (&self) -> &'static str {
+        match *self {
+          $(EventId::$event => stringify!($event)),*
+        }
+      }
+
+      /// Whether the event has an action
+      #[allow(clippy::uninhabited_references)]
+      pub const fn has_action (&self) -> bool {
+        match *self {
+          $(EventId::$event => $crate::is_action (stringify!($($action)*))),*
+        }
+      }
+
       pub fn transition (&self) -> Transition {
         #[allow(clippy::uninhabited_references)]
         match *self {
@@ -2217,6 +3500,15 @@ macro_rules! def_machine_nodefault_debug {
         $($ext_name:ident : $ext_type:ty $(= $ext_default:expr)*),*
       ]
       $(self_reference: $self_reference:ident)*
+      $(logging: {
+        $(target: $log_target:literal)?
+        $(initialize: $log_initialize:ident)?
+        $(accepted: $log_accepted:ident)?
+        $(rejected: $log_rejected:ident)?
+        $(terminate_success: $log_terminate_success:ident)?
+        $(terminate_failure: $log_terminate_failure:ident)?
+      })?
+      $(fuzz: $($fuzz:ident),+)?
       initial_state: $initial:ident $({
         $(initial_action: $initial_action:block)*
       })*
@@ -2228,152 +3520,188 @@ macro_rules! def_machine_nodefault_debug {
 
   ) => {
 
-    $crate::def_machine_debug!{
-      @base
-      machine $machine
-        $(<$($type_var $(: { $($type_constraint),+ })*),+>)*
+    $crate::def_machine!{
+      @validate ($)
+      STATES [
+        $($state ($($data_name),*))+
+      ]
+      EVENTS [
+        $($event <$source> $(=> <$target>)*
+          { $($($state_data),*)* }
+        )*
+      ]
+      initial_state: $initial
+      $(terminal_state: $terminal)*
       {
-        STATES [
-          $(state $state ($($data_name : $data_type $(= $data_default)*),*)
-          $({
-            $(entry $entry)*
-            $(exit  $exit)*
-          })*)+
-        ]
-        EVENTS [
-          $(event $event <$source> $(=> <$target>)*
-            ($($param_name : $param_type $(=> $param_default)*),*)
-            $({$($state_data),*} => $action)*
-          )*
-        ]
-        EXTENDED [
-          $($ext_name : $ext_type $(= $ext_default)*),*
-        ]
-        $(self_reference: $self_reference)*
-        initial_state: $initial $({
-          $(initial_action: $initial_action)*
-        })*
-        $(terminal_state: $terminal $({
-          $(terminate_success: $terminate_success)*
-          $(terminate_failure: $terminate_failure)*
-        })*)*
-      }
-    }
+        $crate::def_machine_debug!{
+          @base
+          machine $machine
+            $(<$($type_var $(: { $($type_constraint),+ })*),+>)*
+          {
+            STATES [
+              $(state $state ($($data_name : $data_type $(= $data_default)*),*)
+              $({
+                $(entry $entry)*
+                $(exit  $exit)*
+              })*)+
+            ]
+            EVENTS [
+              $(event $event <$source> $(=> <$target>)*
+                ($($param_name : $param_type $(=> $param_default)*),*)
+                $({$($state_data),*} => $action)*
+              )*
+            ]
+            EXTENDED [
+              $($ext_name : $ext_type $(= $ext_default)*),*
+            ]
+            $(self_reference: $self_reference)*
+            $(logging: {
+              $(target: $log_target)?
+              $(initialize: $log_initialize)?
+              $(accepted: $log_accepted)?
+              $(rejected: $log_rejected)?
+              $(terminate_success: $log_terminate_success)?
+              $(terminate_failure: $log_terminate_failure)?
+            })?
+            $(fuzz: $($fuzz),+)?
+            initial_state: $initial $({
+              $(initial_action: $initial_action)*
+            })*
+            $(terminal_state: $terminal $({
+              $(terminate_success: $terminate_success)*
+              $(terminate_failure: $terminate_failure)*
+            })*)*
+          }
+        }
 
-    impl $(<$($type_var),+>)* $crate::MachineDotfile
-      for $machine $(<$($type_var),+>)*
-    where
-    $($(
-      $type_var : std::fmt::Debug,
-      $($($type_var : $type_constraint),+)*
-    ),+)*
-    {
-      fn name() -> &'static str {
-        stringify!($machine)
-      }
-      fn type_vars() -> Vec <String> {
-        let mut _v = Vec::new();
+        impl $(<$($type_var),+>)* $crate::MachineDotfile
+          for $machine $(<$($type_var),+>)*
+        where
         $($(
-        _v.push (format!(
-          "{} = {}", stringify!($type_var), std::any::type_name::<$type_var>()));
-        )+)*
-        _v
-      }
-      fn extended_state_names() -> Vec <&'static str> {
-        vec![$(stringify!($ext_name)),*]
-      }
-      fn extended_state_types() -> Vec <&'static str> {
-        vec![$(stringify!($ext_type)),*]
-      }
-      fn extended_state_defaults() -> Vec <&'static str> {
-        vec![$(stringify!($($ext_default)*)),*]
-      }
-      fn self_reference() -> &'static str {
-        stringify!($($self_reference)*)
-      }
-      fn states() -> Vec <&'static str> {
-        vec![$(stringify!($state)),+]
-      }
-      fn state_initial() -> &'static str {
-        stringify!($initial)
-      }
-      fn state_terminal() -> &'static str {
-        stringify!($($terminal)*)
-      }
-      fn state_data_names() -> Vec <Vec <&'static str>> {
-        let mut v = Vec::new();
-        $({
-          let mut _w = Vec::new();
-          $(_w.push (stringify!($data_name));)*
-          v.push (_w);
-        })+
-        v
-      }
-      fn state_data_types() -> Vec <Vec <&'static str>> {
-        let mut v = Vec::new();
-        $({
-          let mut _w = Vec::new();
-          $(_w.push (stringify!($data_type));)*
-          v.push (_w);
-        })+
-        v
-      }
-      fn state_data_defaults() -> Vec <Vec <&'static str>> {
-        let mut v = Vec::new();
-        $({
-          let mut _w = Vec::new();
-          $(_w.push (stringify!($($data_default)*));)*
-          v.push (_w);
-        })+
-        v
-      }
-      /// This version does not evaluate expressions, only pretty prints them
-      fn state_data_pretty_defaults() -> Vec <Vec <String>> {
-        let mut v = Vec::new();
-        $({
-          let mut _w = Vec::new();
-          $(
-          _w.push (format!("{:#?}", stringify!($($data_default)*)));
-          )*
-          v.push (_w);
-        })+
-        v
-      }
+          $type_var : std::fmt::Debug,
+          $($($type_var : $type_constraint),+)*
+        ),+)*
+        {
+          fn name() -> &'static str {
+            stringify!($machine)
+          }
+          fn type_vars() -> Vec <String> {
+            let mut _v = Vec::new();
+            $($(
+            _v.push (format!(
+              "{} = {}", stringify!($type_var), std::any::type_name::<$type_var>()));
+            )+)*
+            _v
+          }
+          fn extended_state_names() -> Vec <&'static str> {
+            vec![$(stringify!($ext_name)),*]
+          }
+          fn extended_state_types() -> Vec <&'static str> {
+            vec![$(stringify!($ext_type)),*]
+          }
+          fn extended_state_defaults() -> Vec <&'static str> {
+            vec![$(stringify!($($ext_default)*)),*]
+          }
+          fn self_reference() -> &'static str {
+            stringify!($($self_reference)*)
+          }
+          fn states() -> Vec <&'static str> {
+            vec![$(stringify!($state)),+]
+          }
+          fn state_initial() -> &'static str {
+            stringify!($initial)
+          }
+          fn state_terminal() -> &'static str {
+            stringify!($($terminal)*)
+          }
+          fn state_data_names() -> Vec <Vec <&'static str>> {
+            vec![$(vec![$(stringify!($data_name)),*]),+]
+          }
+          fn state_data_types() -> Vec <Vec <&'static str>> {
+            vec![$(vec![$(stringify!($data_type)),*]),+]
+          }
+          fn state_data_defaults() -> Vec <Vec <&'static str>> {
+            let mut v = Vec::new();
+            $({
+              let mut _w = Vec::new();
+              $(_w.push (stringify!($($data_default)*));)*
+              v.push (_w);
+            })+
+            v
+          }
+          /// This version does not evaluate expressions, only pretty prints them
+          fn state_data_pretty_defaults() -> Vec <Vec <String>> {
+            let mut v = Vec::new();
+            $({
+              let mut _w = Vec::new();
+              $(
+              _w.push (format!("{:#?}", stringify!($($data_default)*)));
+              )*
+              v.push (_w);
+            })+
+            v
+          }
 
-      fn events() -> Vec <&'static str> {
-        vec![$(stringify!($event)),*]
-      }
-      fn event_sources() -> Vec <&'static str> {
-        vec![$(stringify!($source)),*]
-      }
-      fn event_targets() -> Vec <&'static str> {
-        vec![$(stringify!($($target)*)),*]
-      }
-      fn event_actions() -> Vec <&'static str> {
-        vec![$(stringify!($($action)*)),*]
-      }
-    }
+          fn events() -> Vec <&'static str> {
+            vec![$(stringify!($event)),*]
+          }
+          fn event_sources() -> Vec <&'static str> {
+            vec![$(stringify!($source)),*]
+          }
+          fn event_targets() -> Vec <&'static str> {
+            vec![$(stringify!($($target)*)),*]
+          }
+          fn event_actions() -> Vec <&'static str> {
+            vec![$(stringify!($($action)*)),*]
+          }
+          fn event_param_names() -> Vec <Vec <&'static str>> {
+            vec![$(vec![$(stringify!($param_name)),*]),*]
+          }
+          fn event_param_types() -> Vec <Vec <&'static str>> {
+            vec![$(vec![$(stringify!($param_type)),*]),*]
+          }
+          fn event_param_defaults() -> Vec <Vec <&'static str>> {
+            vec![$(vec![$(stringify!($($param_default)*)),*]),*]
+          }
+          fn state_entry_actions() -> Vec <&'static str> {
+            vec![$(stringify!($($($entry)*)*)),+]
+          }
+          fn state_exit_actions() -> Vec <&'static str> {
+            vec![$(stringify!($($($exit)*)*)),+]
+          }
+          fn initial_action() -> &'static str {
+            stringify!($($($initial_action)*)*)
+          }
+          fn terminate_success_action() -> &'static str {
+            stringify!($($($($terminate_success)*)*)*)
+          }
+          fn terminate_failure_action() -> &'static str {
+            stringify!($($($($terminate_failure)*)*)*)
+          }
+        }
 
-    impl $(<$($type_var),+>)* ExtendedState $(<$($type_var),+>)* where
-    $($(
-      $type_var : std::fmt::Debug,
-      $($($type_var : $type_constraint),+)*
-    ),+)*
-    {
-      /// Creation method that allows overriding defaults. If a field does not
-      /// have a default specified it is a required argument.
-      // TODO: indicate which arguments are missing in case of failure
-      // TODO: make required arguments non-Option types?
-      pub fn new ($($ext_name : Option <$ext_type>),*) -> Option <Self> {
-        Some (Self {
-          $($ext_name: {
-            if let Some ($ext_name) = $ext_name {
-              $ext_name
-            } else {
-              $crate::def_machine_debug!(@expr_option $($ext_default)*)?
-            }
-          }),*
-        })
+        impl $(<$($type_var),+>)* ExtendedState $(<$($type_var),+>)* where
+        $($(
+          $type_var : std::fmt::Debug,
+          $($($type_var : $type_constraint),+)*
+        ),+)*
+        {
+          /// Creation method that allows overriding defaults. If a field does not
+          /// have a default specified it is a required argument.
+          // TODO: indicate which arguments are missing in case of failure
+          // TODO: make required arguments non-Option types?
+          pub fn new ($($ext_name : Option <$ext_type>),*) -> Option <Self> {
+            Some (Self {
+              $($ext_name: {
+                if let Some ($ext_name) = $ext_name {
+                  $ext_name
+                } else {
+                  $crate::def_machine_debug!(@expr_option $($ext_default)*)?
+                }
+              }),*
+            })
+          }
+        }
       }
     }
 
@@ -2405,6 +3733,15 @@ macro_rules! def_machine_nodefault_debug {
           $({ $($state_data:ident),* } => $action:block)*
         )*
       ]
+      $(logging: {
+        $(target: $log_target:literal)?
+        $(initialize: $log_initialize:ident)?
+        $(accepted: $log_accepted:ident)?
+        $(rejected: $log_rejected:ident)?
+        $(terminate_success: $log_terminate_success:ident)?
+        $(terminate_failure: $log_terminate_failure:ident)?
+      })?
+      $(fuzz: $($fuzz:ident),+)?
       initial_state: $initial:ident $({
         $(initial_action: $initial_action:block)*
       })*
@@ -2435,6 +3772,15 @@ macro_rules! def_machine_nodefault_debug {
           $($($ext_name : $ext_type $(= $ext_default)*),*)*
         ]
         $(self_reference: $self_reference)*
+        $(logging: {
+          $(target: $log_target)?
+          $(initialize: $log_initialize)?
+          $(accepted: $log_accepted)?
+          $(rejected: $log_rejected)?
+          $(terminate_success: $log_terminate_success)?
+          $(terminate_failure: $log_terminate_failure)?
+        })?
+        $(fuzz: $($fuzz),+)?
         initial_state: $initial $({
           $(initial_action: $initial_action)*
         })*
@@ -2448,3 +3794,269 @@ macro_rules! def_machine_nodefault_debug {
   };
 
 } // end def_machine_nodefault_debug!
+
+/// Drive a machine through a sequence of events, checking the outcome of each.
+///
+/// Each step is an event ID (with default parameters) or a parenthesized
+/// `Event` expression, followed by `=>` and either the expected state ID or
+/// `Err (exception)` with the expected `HandleEventException` variant. The
+/// `EventId` and `StateId` types of the machine must be in scope:
+///
+/// ```text
+/// assert_machine_scenario!{ door,
+///   Knock => Closed,
+///   Open  => Opened,
+///   Open  => Err (WrongState),
+///   (Event::from (EventId::Close)) => Closed
+/// }
+/// ```
+///
+/// On failure the panic message shows the step number, the event and the
+/// state of the machine.
+#[macro_export]
+macro_rules! assert_machine_scenario {
+  ( $machine:expr,
+    $($event:tt => $result:ident $(($exception:ident))?),* $(,)?
+  ) => {{
+    let machine = &mut $machine;
+    let mut step = 0usize;
+    $(
+      $crate::assert_machine_scenario!(@step machine step
+        $event => $result $(($exception))?);
+    )*
+  }};
+
+  ( @step $machine:ident $step:ident
+    $event:ident => Err ($exception:ident)
+  ) => {
+    $crate::assert_machine_scenario!(@step $machine $step
+      (Event::from (EventId::$event)) => Err ($exception));
+  };
+
+  ( @step $machine:ident $step:ident $event:ident => $state:ident ) => {
+    $crate::assert_machine_scenario!(@step $machine $step
+      (Event::from (EventId::$event)) => $state);
+  };
+
+  ( @step $machine:ident $step:ident
+    ($event:expr) => Err ($exception:ident)
+  ) => {
+    $step += 1;
+    let event : Event = $event;
+    let event_id = event.id().clone();
+    let state = $machine.state_id();
+    match $machine.handle_event (event) {
+      Ok (()) => panic!(
+        "scenario step {}: event {} in state {}: expected {} but the event \
+        was accepted with new state {}",
+        $step, event_id.name(), state.name(), stringify!($exception),
+        $machine.state_id().name()),
+      Err (exception) => assert!(
+        matches!(exception, $crate::HandleEventException::$exception),
+        "scenario step {}: event {} in state {}: expected {} but got {:?}",
+        $step, event_id.name(), state.name(), stringify!($exception), exception)
+    }
+  };
+
+  ( @step $machine:ident $step:ident ($event:expr) => $state:ident ) => {
+    $step += 1;
+    let event : Event = $event;
+    let event_id = event.id().clone();
+    let state = $machine.state_id();
+    if let Err (exception) = $machine.handle_event (event) {
+      panic!("scenario step {}: event {} in state {}: expected state {} but \
+        got {:?}",
+        $step, event_id.name(), state.name(), stringify!($state), exception)
+    }
+    assert!($machine.state_id() == StateId::$state,
+      "scenario step {}: event {} in state {}: expected state {} but got state \
+      {}",
+      $step, event_id.name(), state.name(), stringify!($state),
+      $machine.state_id().name());
+  };
+}
+
+/// Implementation detail of the `def_machine` macros: `arbitrary::Arbitrary`
+/// implementations for the event types of machines with `arbitrary` listed in
+/// their `fuzz` clause.
+#[cfg(feature = "arbitrary")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! def_machine_arbitrary {
+  ( [arbitrary $($fuzz:ident)*]
+    EVENTS [
+      $($event:ident ($($param_name:ident : $param_type:ty),*))*
+    ]
+  ) => {
+
+    impl <'event> $crate::arbitrary::Arbitrary <'event> for EventId {
+      fn arbitrary (u : &mut $crate::arbitrary::Unstructured <'event>)
+        -> $crate::arbitrary::Result <Self>
+      {
+        u.choose (EventId::ALL).cloned()
+      }
+    }
+
+    impl <'event> $crate::arbitrary::Arbitrary <'event> for EventParams <'event> {
+      fn arbitrary (u : &mut $crate::arbitrary::Unstructured <'event>)
+        -> $crate::arbitrary::Result <Self>
+      {
+        Event::arbitrary (u).map (|event| event.params)
+      }
+    }
+
+    impl <'event> $crate::arbitrary::Arbitrary <'event> for Event <'event> {
+      // if no events are defined the event ID type is uninhabited
+      #[allow(unreachable_code, unused_variables)]
+      fn arbitrary (u : &mut $crate::arbitrary::Unstructured <'event>)
+        -> $crate::arbitrary::Result <Self>
+      {
+        let id : EventId = u.arbitrary()?;
+        let params = match id {
+          $(EventId::$event => EventParams::$event {
+            $($param_name: u.arbitrary()?),*
+          }),*
+        };
+        Ok (Event { id, params })
+      }
+    }
+
+  };
+  ( [$other:ident $($fuzz:ident)*] $($tokens:tt)* ) => {
+    $crate::def_machine_arbitrary!{ [$($fuzz)*] $($tokens)* }
+  };
+
+  ( [] $($tokens:tt)* ) => {};
+}
+
+#[cfg(not(feature = "arbitrary"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! def_machine_arbitrary {
+  ( [arbitrary $($fuzz:ident)*] $($tokens:tt)* ) => {
+    compile_error!(
+      "`fuzz: arbitrary` requires the `arbitrary` feature of macro-machines");
+  };
+
+  ( [$other:ident $($fuzz:ident)*] $($tokens:tt)* ) => {
+    $crate::def_machine_arbitrary!{ [$($fuzz)*] $($tokens)* }
+  };
+
+  ( [] $($tokens:tt)* ) => {};
+}
+
+/// Implementation detail of the `def_machine` macros: `proptest` strategies
+/// for the event types of machines with `proptest` listed in their `fuzz`
+/// clause.
+///
+/// Strategies for events and event parameters are only generated for the
+/// `_debug` variants since proptest requires values to implement `Debug`.
+#[cfg(feature = "proptest")]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! def_machine_proptest {
+  ( [proptest $($fuzz:ident)*] @event_id ) => {
+
+    impl EventId {
+      /// Strategy selecting among all event IDs
+      ///
+      /// # Panics
+      ///
+      /// Panics if no events are defined.
+      #[allow(dead_code)]
+      pub fn strategy()
+        -> impl $crate::proptest::strategy::Strategy <Value=Self>
+      {
+        $crate::proptest::sample::select (EventId::ALL)
+      }
+    }
+
+  };
+
+  ( [proptest $($fuzz:ident)*]
+    EVENTS [
+      $($event:ident ($($param_name:ident : $param_type:ty),*))*
+    ]
+  ) => {
+
+    $crate::def_machine_proptest!([proptest] @event_id);
+
+    // event parameter types may refer to the `'event` lifetime
+    #[allow(redundant_lifetimes)]
+    impl <'event> Event <'event> where 'event : 'static {
+      /// Strategy for events with arbitrary parameters
+      ///
+      /// # Panics
+      ///
+      /// Panics if no events are defined.
+      #[allow(dead_code)]
+      pub fn strategy() -> $crate::proptest::strategy::BoxedStrategy <Self> {
+        use $crate::proptest::strategy::Strategy;
+        $crate::proptest::strategy::Union::<
+          $crate::proptest::strategy::BoxedStrategy <Self>
+        >::new (vec![$(
+          (
+            $($crate::proptest::arbitrary::any::<$param_type>(),)*
+            $crate::proptest::strategy::Just (()),
+          ).prop_map (|($($param_name,)* (),)| Event {
+            id:     EventId::$event,
+            params: EventParams::$event { $($param_name),* }
+          }).boxed()
+        ),*]).boxed()
+      }
+
+      /// Strategy for sequences of events with arbitrary parameters
+      ///
+      /// # Panics
+      ///
+      /// Panics if no events are defined.
+      #[allow(dead_code)]
+      pub fn sequence_strategy <S> (size : S)
+        -> $crate::proptest::strategy::BoxedStrategy <Vec <Self>>
+      where
+        S : Into <$crate::proptest::collection::SizeRange>
+      {
+        use $crate::proptest::strategy::Strategy;
+        $crate::proptest::collection::vec (Self::strategy(), size).boxed()
+      }
+    }
+
+    // event parameter types may refer to the `'event` lifetime
+    #[allow(redundant_lifetimes)]
+    impl <'event> EventParams <'event> where 'event : 'static {
+      /// Strategy for arbitrary event parameters
+      ///
+      /// # Panics
+      ///
+      /// Panics if no events are defined.
+      #[allow(dead_code)]
+      pub fn strategy() -> $crate::proptest::strategy::BoxedStrategy <Self> {
+        use $crate::proptest::strategy::Strategy;
+        Event::strategy().prop_map (|event| event.params).boxed()
+      }
+    }
+
+  };
+
+  ( [$other:ident $($fuzz:ident)*] $($tokens:tt)* ) => {
+    $crate::def_machine_proptest!{ [$($fuzz)*] $($tokens)* }
+  };
+
+  ( [] $($tokens:tt)* ) => {};
+}
+
+#[cfg(not(feature = "proptest"))]
+#[doc(hidden)]
+#[macro_export]
+macro_rules! def_machine_proptest {
+  ( [proptest $($fuzz:ident)*] $($tokens:tt)* ) => {
+    compile_error!(
+      "`fuzz: proptest` requires the `proptest` feature of macro-machines");
+  };
+
+  ( [$other:ident $($fuzz:ident)*] $($tokens:tt)* ) => {
+    $crate::def_machine_proptest!{ [$($fuzz)*] $($tokens)* }
+  };
+
+  ( [] $($tokens:tt)* ) => {};
+}
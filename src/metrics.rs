@@ -0,0 +1,284 @@
+//! Optional runtime metrics for state machine instances.
+//!
+//! Metrics collection is enabled per instance with `enable_metrics`, after
+//! which the machine records for each event the number of times it was
+//! accepted and rejected, and for each state the number of times it was
+//! entered and the cumulative time spent in it. A `Snapshot` of the current
+//! values is returned by the `metrics` method of the machine:
+//!
+//! ```text
+//! door.enable_metrics (Box::new (StdClock::new()));
+//! door.handle_event (EventId::Knock.into()).unwrap();
+//! print!("{}", door.metrics().unwrap().to_prometheus());
+//! ```
+//!
+//! Snapshots of several machines or instances are exposed together with
+//! `to_prometheus`, which writes the description of each metric only once.
+
+use std::time::{Duration, Instant};
+
+/// Source of timestamps used to measure the time spent in each state
+pub trait Clock {
+  /// Time elapsed since an arbitrary fixed epoch
+  fn now (&self) -> Duration;
+}
+
+/// Closures returning the time since a fixed epoch may be used as clocks, e.g.
+/// for testing
+impl <F : Fn() -> Duration> Clock for F {
+  fn now (&self) -> Duration {
+    self()
+  }
+}
+
+/// Monotonic clock measuring time since its creation
+#[derive(Clone, Copy, Debug)]
+pub struct StdClock {
+  epoch : Instant
+}
+
+impl StdClock {
+  pub fn new() -> Self {
+    StdClock { epoch: Instant::now() }
+  }
+}
+
+impl Default for StdClock {
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl Clock for StdClock {
+  fn now (&self) -> Duration {
+    self.epoch.elapsed()
+  }
+}
+
+/// Accumulates metrics for a single machine instance.
+///
+/// States and events are identified by the index of their ID variant.
+pub struct Collector {
+  clock          : Box <dyn Clock + Send + Sync>,
+  event_accepted : Vec <u64>,
+  event_rejected : Vec <u64>,
+  state_entries  : Vec <u64>,
+  state_time     : Vec <Duration>,
+  /// Index of the current state and the time it was entered
+  current        : Option <(usize, Duration)>
+}
+
+impl Collector {
+  pub fn new (
+    clock : Box <dyn Clock + Send + Sync>, state_count : usize, event_count : usize
+  ) -> Self {
+    Collector {
+      clock,
+      event_accepted: vec![0; event_count],
+      event_rejected: vec![0; event_count],
+      state_entries:  vec![0; state_count],
+      state_time:     vec![Duration::ZERO; state_count],
+      current:        None
+    }
+  }
+
+  pub fn event_accepted (&mut self, event : usize) {
+    self.event_accepted[event] += 1;
+  }
+
+  pub fn event_rejected (&mut self, event : usize) {
+    self.event_rejected[event] += 1;
+  }
+
+  pub fn state_entered (&mut self, state : usize) {
+    self.state_entries[state] += 1;
+    self.current = Some ((state, self.clock.now()));
+  }
+
+  pub fn state_exited (&mut self) {
+    if let Some ((state, entered)) = self.current.take() {
+      self.state_time[state] += self.clock.now().saturating_sub (entered);
+    }
+  }
+
+  /// Current values, including the time spent so far in the current state.
+  ///
+  /// State and event names are given in order of their ID variants.
+  pub fn snapshot (&self,
+    machine : &'static str,
+    instance : Option <&str>,
+    states   : &[&'static str],
+    events   : &[&'static str]
+  ) -> Snapshot {
+    debug_assert_eq!(states.len(), self.state_entries.len());
+    debug_assert_eq!(events.len(), self.event_accepted.len());
+    let now = self.clock.now();
+    let events = events.iter().enumerate().map (|(i, event)| EventStats {
+      event,
+      accepted: self.event_accepted[i],
+      rejected: self.event_rejected[i]
+    }).collect();
+    let states = states.iter().enumerate().map (|(i, state)| {
+      let mut time = self.state_time[i];
+      let mut current = false;
+      match self.current {
+        Some ((state_current, entered)) if state_current == i => {
+          time += now.saturating_sub (entered);
+          current = true;
+        }
+        _ => {}
+      }
+      StateStats { state, entries: self.state_entries[i], time, current }
+    }).collect();
+    Snapshot {
+      machine,
+      instance: instance.map (str::to_string),
+      events,
+      states
+    }
+  }
+}
+
+impl std::fmt::Debug for Collector {
+  fn fmt (&self, f : &mut std::fmt::Formatter) -> std::fmt::Result {
+    f.debug_struct ("Collector")
+      .field ("event_accepted", &self.event_accepted)
+      .field ("event_rejected", &self.event_rejected)
+      .field ("state_entries",  &self.state_entries)
+      .field ("state_time",     &self.state_time)
+      .field ("current",        &self.current)
+      .finish_non_exhaustive()
+  }
+}
+
+/// Metrics of a machine instance at the time of the snapshot
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Snapshot {
+  pub machine  : &'static str,
+  pub instance : Option <String>,
+  pub events   : Vec <EventStats>,
+  pub states   : Vec <StateStats>
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EventStats {
+  pub event    : &'static str,
+  /// Number of times the event was handled
+  pub accepted : u64,
+  /// Number of times the event raised a `HandleEventException`
+  pub rejected : u64
+}
+
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StateStats {
+  pub state   : &'static str,
+  /// Number of times the state was entered
+  pub entries : u64,
+  /// Cumulative time spent in the state
+  pub time    : Duration,
+  /// Whether this is the current state of the machine
+  pub current : bool
+}
+
+impl EventStats {
+  /// Fraction of occurrences of the event that were rejected, or zero if the
+  /// event has not occurred
+  #[expect(clippy::cast_precision_loss)]
+  pub fn rejection_rate (&self) -> f64 {
+    let total = self.accepted + self.rejected;
+    if total == 0 {
+      0.0
+    } else {
+      self.rejected as f64 / total as f64
+    }
+  }
+}
+
+impl Snapshot {
+  /// Format as Prometheus text exposition format; see `to_prometheus` for
+  /// exposing several instances together
+  pub fn to_prometheus (&self) -> String {
+    to_prometheus (std::slice::from_ref (self))
+  }
+
+  /// Label set identifying the machine and instance
+  fn labels (&self) -> String {
+    use std::fmt::Write;
+    let mut labels = format!("machine=\"{}\"", escape_label (self.machine));
+    if let Some (instance) = self.instance.as_ref() {
+      write!(labels, ",instance=\"{}\"", escape_label (instance)).unwrap();
+    }
+    labels
+  }
+}
+
+impl std::fmt::Display for Snapshot {
+  fn fmt (&self, f : &mut std::fmt::Formatter) -> std::fmt::Result {
+    write!(f, "{}", self.machine)?;
+    if let Some (instance) = self.instance.as_ref() {
+      write!(f, " ({instance})")?;
+    }
+    writeln!(f)?;
+    let width = self.events.iter().map (|e| e.event.len())
+      .chain (self.states.iter().map (|s| s.state.len()))
+      .max().unwrap_or (0);
+    for e in &self.events {
+      writeln!(f, "  event {:width$}  accepted: {}  rejected: {}",
+        e.event, e.accepted, e.rejected)?;
+    }
+    for s in &self.states {
+      writeln!(f, "  state {:width$}  entries: {}  time: {:?}{}",
+        s.state, s.entries, s.time, if s.current { "  (current)" } else { "" })?;
+    }
+    Ok (())
+  }
+}
+
+/// Format the snapshots of any number of machines or instances as a single
+/// Prometheus text exposition, with the `HELP` and `TYPE` lines of each metric
+/// family written once followed by the samples of every snapshot:
+///
+/// ```text
+/// let snapshots = [door1.metrics().unwrap(), door2.metrics().unwrap()];
+/// print!("{}", metrics::to_prometheus (&snapshots));
+/// ```
+pub fn to_prometheus (snapshots : &[Snapshot]) -> String {
+  use std::fmt::Write;
+  // label key, label value and sample value of each sample in a snapshot
+  type Samples = fn (&Snapshot) -> Vec <(&'static str, &'static str, String)>;
+  let families : [(&str, &str, &str, Samples); 5] = [
+    ("event_accepted_total", "counter", "Events handled",
+      |m| m.events.iter().map (
+        |e| ("event", e.event, e.accepted.to_string())).collect()),
+    ("event_rejected_total", "counter", "Events rejected",
+      |m| m.events.iter().map (
+        |e| ("event", e.event, e.rejected.to_string())).collect()),
+    ("state_entries_total", "counter", "State entries",
+      |m| m.states.iter().map (
+        |st| ("state", st.state, st.entries.to_string())).collect()),
+    ("state_seconds_total", "counter", "Time spent in state",
+      |m| m.states.iter().map (
+        |st| ("state", st.state, st.time.as_secs_f64().to_string())).collect()),
+    ("state_current", "gauge", "Current state",
+      |m| m.states.iter().map (
+        |st| ("state", st.state, u8::from (st.current).to_string())).collect())
+  ];
+  let labels = snapshots.iter().map (Snapshot::labels).collect::<Vec <_>>();
+  let mut s = String::new();
+  for (name, kind, help, samples) in families {
+    writeln!(s, "# HELP macro_machines_{name} {help}").unwrap();
+    writeln!(s, "# TYPE macro_machines_{name} {kind}").unwrap();
+    for (snapshot, labels) in snapshots.iter().zip (&labels) {
+      for (key, value, sample) in samples (snapshot) {
+        writeln!(s, "macro_machines_{name}{{{labels},{key}=\"{}\"}} {sample}",
+          escape_label (value)).unwrap();
+      }
+    }
+  }
+  s
+}
+
+/// Escape a Prometheus label value
+fn escape_label (value : &str) -> String {
+  value.replace ('\\', "\\\\").replace ('"', "\\\"").replace ('\n', "\\n")
+}
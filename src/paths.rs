@@ -0,0 +1,344 @@
+//! Shortest event paths between states, e.g. for generating test inputs.
+//!
+//! Paths are sequences of event IDs following the declared transitions;
+//! universal events lead from every state and internal events never change
+//! the state so they are never part of a shortest path. For machines with
+//! default event parameters, `Event::path` and `Event::paths` return events
+//! ready to be passed to `handle_event`:
+//!
+//! ```text
+//! for event in Event::path (&StateId::Closed, &StateId::Locked).unwrap() {
+//!   door.handle_event (event).unwrap();
+//! }
+//! ```
+//!
+//! A transition tour is a sequence of events exercising every transition of
+//! the machine; machines with default extended state and event parameters can
+//! be run through their tour with `assert_transition_tour`:
+//!
+//! ```text
+//! #[test]
+//! fn door_transitions() {
+//!   Door::assert_transition_tour();
+//! }
+//! ```
+
+use crate::MachineMetadata;
+
+/// Shortest sequence of events leading from state `from` to state `to` of
+/// machine `M`, or `None` if `to` can not be reached.
+///
+/// The path from a state to itself is empty.
+pub fn shortest <M : MachineMetadata> (from : &M::StateId, to : &M::StateId)
+  -> Option <Vec <M::EventId>>
+{
+  shortest_in (M::STATES, M::TRANSITIONS, from, to)
+}
+
+/// Shortest sequences of events leading from the initial state of machine `M`
+/// to each reachable state, in order of state definition
+pub fn from_initial <M : MachineMetadata> ()
+  -> Vec <(M::StateId, Vec <M::EventId>)>
+{
+  from_state_in (M::STATES, M::TRANSITIONS, &M::STATE_INITIAL)
+}
+
+/// Shortest path over the given states and transition table (see
+/// `MachineMetadata::TRANSITIONS`)
+///
+/// # Panics
+///
+/// Panics if a state of the transition table is missing from `states`.
+pub fn shortest_in <S : Clone + Eq, E : Clone> (
+  states      : &[S],
+  transitions : &[(E, Option <S>, Option <S>)],
+  from        : &S,
+  to          : &S
+) -> Option <Vec <E>> {
+  let index = states.iter().position (|s| s == to)?;
+  search (states, transitions, from).swap_remove (index)
+}
+
+/// Shortest paths from the `from` state to each reachable state over the given
+/// states and transition table (see `MachineMetadata::TRANSITIONS`)
+///
+/// # Panics
+///
+/// Panics if a state of the transition table is missing from `states`.
+pub fn from_state_in <S : Clone + Eq, E : Clone> (
+  states      : &[S],
+  transitions : &[(E, Option <S>, Option <S>)],
+  from        : &S
+) -> Vec <(S, Vec <E>)> {
+  states.iter().zip (search (states, transitions, from))
+    .filter_map (|(state, path)| path.map (|path| (state.clone(), path)))
+    .collect()
+}
+
+/// Sequence of events starting from the initial state of machine `M` that
+/// exercises each transition at least once, paired with the state expected
+/// after each event; see `tour_in`
+pub fn tour <M : MachineMetadata> () -> Vec <(M::EventId, M::StateId)> {
+  tour_in (M::STATES, M::TRANSITIONS, &M::STATE_INITIAL,
+    M::STATE_TERMINAL.as_ref())
+}
+
+/// Transition tour over the given states and transition table (see
+/// `MachineMetadata::TRANSITIONS`) starting from the `initial` state.
+///
+/// Each step is an event and the state expected after handling it. Every
+/// transition from each state that can be reached is covered, with universal
+/// events taken from each of these states. The tour is a shortest such
+/// sequence, found as a directed Chinese postman walk: the transitions that
+/// must be repeated are chosen by a minimum cost flow balancing the states and
+/// the walk is an Euler path of the resulting multigraph. If a `terminal`
+/// state is given and can be reached the tour ends there, and otherwise in
+/// whichever state gives the shortest tour.
+///
+/// When no single walk covers every transition, e.g. when the tour has to
+/// choose between two states that can not both be reached, it instead
+/// greedily takes an uncovered transition from the current state if there is
+/// one and otherwise follows a shortest path to the nearest state that has one.
+///
+/// # Panics
+///
+/// Panics if a state of the transition table is missing from `states`.
+pub fn tour_in <S : Clone + Eq, E : Clone> (
+  states      : &[S],
+  transitions : &[(E, Option <S>, Option <S>)],
+  initial     : &S,
+  terminal    : Option <&S>
+) -> Vec <(E, S)> {
+  let index = |state : &S| states.iter().position (|s| s == state)
+    .expect ("state should be in states");
+  let reachable = search_rows (states, transitions, initial);
+  // transitions to be covered as (source, row, target) in order of definition
+  let edges = (0..states.len()).filter (|source| reachable[*source].is_some())
+    .flat_map (|source| transitions.iter().enumerate()
+      .filter (move |(_, (_, from, _))|
+        from.as_ref().is_none_or (|from| index (from) == source))
+      .map (move |(row, (_, _, target))|
+        (source, row, target.as_ref().map_or (source, index))))
+    .collect::<Vec <_>>();
+  let start = index (initial);
+  let ends = match terminal.map (index) {
+    Some (end) if reachable[end].is_some() => vec![end],
+    _ => (0..states.len()).filter (|end| reachable[*end].is_some()).collect()
+  };
+  let rows = ends.into_iter()
+    .filter_map (|end| balance (states.len(), &edges, start, end))
+    .min_by_key (Vec::len)
+    .map_or_else (
+      || greedy_tour (states, transitions, &edges, start, terminal.map (index)),
+      |extra| euler_path (states.len(), edges.iter().copied().chain (extra),
+        start));
+  rows.into_iter()
+    .map (|(row, target)| (transitions[row].0.clone(), states[target].clone()))
+    .collect()
+}
+
+/// Transitions that must be repeated so that a walk from `start` to `end`
+/// taking each of the `edges` at least once exists, or `None` if there is no
+/// such walk; the repeated transitions are the minimum cost flow from the
+/// states with more incoming than outgoing transitions to those with more
+/// outgoing than incoming, found by successive shortest paths
+fn balance (
+  state_count : usize,
+  edges       : &[(usize, usize, usize)],
+  start       : usize,
+  end         : usize
+) -> Option <Vec <(usize, usize, usize)>> {
+  // surplus of outgoing transitions needed by each state
+  let mut supply = vec![0isize; state_count];
+  supply[start] += 1;
+  supply[end]   -= 1;
+  for (source, _, target) in edges {
+    supply[*source] -= 1;
+    supply[*target] += 1;
+  }
+  // first transition between each pair of distinct states, with its flow
+  let mut arcs : Vec <((usize, usize, usize), usize)> = Vec::new();
+  for edge@(source, _, target) in edges {
+    if source != target &&
+      !arcs.iter().any (|((s, _, t), _)| s == source && t == target)
+    {
+      arcs.push ((*edge, 0));
+    }
+  }
+  while supply.iter().any (|s| *s > 0) {
+    // Bellman-Ford from every state with remaining supply over the residual
+    // arcs, where reversing a unit of flow has cost -1
+    let mut dist : Vec <Option <isize>> = supply.iter()
+      .map (|s| (*s > 0).then_some (0)).collect();
+    let mut via : Vec <Option <(usize, bool)>> = vec![None; state_count];
+    for _ in 0..state_count {
+      let mut changed = false;
+      for (arc, ((source, _, target), flow)) in arcs.iter().enumerate() {
+        let mut relax = |from, to : usize, cost : isize, reverse| {
+          let Some (d) = dist[from] else {
+            return
+          };
+          if dist[to].is_none_or (|t| d + cost < t) {
+            dist[to] = Some (d + cost);
+            via[to]  = Some ((arc, reverse));
+            changed  = true;
+          }
+        };
+        relax (*source, *target, 1, false);
+        if *flow > 0 {
+          relax (*target, *source, -1, true);
+        }
+      }
+      if !changed {
+        break
+      }
+    }
+    let sink = (0..state_count)
+      .filter (|s| supply[*s] < 0 && dist[*s].is_some())
+      .min_by_key (|s| dist[*s])?;
+    // augment one unit along the path back to a state with supply
+    let mut state = sink;
+    while let Some ((arc, reverse)) = via[state] {
+      let ((source, _, target), flow) = &mut arcs[arc];
+      if reverse {
+        *flow -= 1;
+        state = *target;
+      } else {
+        *flow += 1;
+        state = *source;
+      }
+    }
+    supply[state] -= 1;
+    supply[sink]  += 1;
+  }
+  Some (arcs.into_iter()
+    .flat_map (|(edge, flow)| std::iter::repeat_n (edge, flow)).collect())
+}
+
+/// Euler path from `start` taking each of the edges, as the row of each step
+/// and the state after it; edges leaving a state are taken in the given order
+/// where possible (Hierholzer's algorithm)
+fn euler_path (
+  state_count : usize,
+  edges       : impl Iterator <Item=(usize, usize, usize)>,
+  start       : usize
+) -> Vec <(usize, usize)> {
+  let mut adjacent = vec![Vec::new(); state_count];
+  for (source, row, target) in edges {
+    adjacent[source].push ((row, target));
+  }
+  let mut next  = vec![0; state_count];
+  let mut stack = vec![(None, start)];
+  let mut path  = Vec::new();
+  while let Some ((row, state)) = stack.last().copied() {
+    if let Some (edge) = adjacent[state].get (next[state]) {
+      next[state] += 1;
+      stack.push ((Some (edge.0), edge.1));
+    } else {
+      stack.pop();
+      if let Some (row) = row {
+        path.push ((row, state));
+      }
+    }
+  }
+  path.reverse();
+  path
+}
+
+/// Greedy walk taking an uncovered edge from the current state if there is one
+/// and otherwise following a shortest path to the nearest state that has one,
+/// then a shortest path to the `terminal` state if it can be reached
+fn greedy_tour <S : Clone + Eq, E> (
+  states      : &[S],
+  transitions : &[(E, Option <S>, Option <S>)],
+  edges       : &[(usize, usize, usize)],
+  start       : usize,
+  terminal    : Option <usize>
+) -> Vec <(usize, usize)> {
+  let mut covered = vec![false; edges.len()];
+  let mut current = start;
+  let mut steps   = Vec::new();
+  let mut take    = |row : usize, current : &mut usize, covered : &mut [bool]| {
+    let edge = edges.iter()
+      .position (|(source, r, _)| *source == *current && *r == row).unwrap();
+    covered[edge] = true;
+    *current = edges[edge].2;
+    steps.push ((row, *current));
+  };
+  loop {
+    let uncovered = |state : usize| edges.iter().zip (covered.iter())
+      .find (|((source, _, _), covered)| *source == state && !**covered)
+      .map (|((_, row, _), _)| *row);
+    if let Some (row) = uncovered (current) {
+      take (row, &mut current, &mut covered);
+      continue
+    }
+    let Some (path) = search_rows (states, transitions, &states[current])
+      .into_iter().enumerate()
+      .filter_map (|(state, path)|
+        path.filter (|_| uncovered (state).is_some()))
+      .min_by_key (Vec::len)
+    else {
+      break
+    };
+    for row in path {
+      take (row, &mut current, &mut covered);
+    }
+  }
+  if let Some (terminal) = terminal {
+    let mut paths = search_rows (states, transitions, &states[current]);
+    if let Some (path) = paths.swap_remove (terminal) {
+      for row in path {
+        take (row, &mut current, &mut covered);
+      }
+    }
+  }
+  steps
+}
+
+/// Breadth-first search returning the shortest path to each state by index
+fn search <S : Clone + Eq, E : Clone> (
+  states      : &[S],
+  transitions : &[(E, Option <S>, Option <S>)],
+  from        : &S
+) -> Vec <Option <Vec <E>>> {
+  search_rows (states, transitions, from).into_iter()
+    .map (|path| path.map (|path|
+      path.into_iter().map (|row| transitions[row].0.clone()).collect()))
+    .collect()
+}
+
+/// Breadth-first search returning the shortest path to each state by index as
+/// indices into the transition table; transitions are explored in order of
+/// definition
+fn search_rows <S : Clone + Eq, E> (
+  states      : &[S],
+  transitions : &[(E, Option <S>, Option <S>)],
+  from        : &S
+) -> Vec <Option <Vec <usize>>> {
+  let index = |state : &S| states.iter().position (|s| s == state)
+    .expect ("state should be in states");
+  let mut paths : Vec <Option <Vec <usize>>> = vec![None; states.len()];
+  let Some (start) = states.iter().position (|s| s == from) else {
+    return paths
+  };
+  paths[start] = Some (Vec::new());
+  let mut queue = std::collections::VecDeque::from ([start]);
+  while let Some (current) = queue.pop_front() {
+    for (row, (_, source, target)) in transitions.iter().enumerate() {
+      let Some (target) = target.as_ref().map (index) else {
+        continue   // internal event
+      };
+      if source.as_ref().is_some_and (|source| index (source) != current) ||
+        paths[target].is_some()
+      {
+        continue
+      }
+      let mut path = paths[current].clone().unwrap();
+      path.push (row);
+      paths[target] = Some (path);
+      queue.push_back (target);
+    }
+  }
+  paths
+}
@@ -0,0 +1,293 @@
+//! Generate `def_machine!` skeletons from SCXML documents.
+//!
+//! Requires the `scxml` feature. The `scxml2machine` binary prints the
+//! skeleton for an SCXML file, and `to_def_machine` may be used from a build
+//! script to generate a file that is included in the crate:
+//!
+//! ```text
+//! // build.rs
+//! let scxml = std::fs::read_to_string ("door.scxml").unwrap();
+//! let out   = std::path::Path::new (&std::env::var ("OUT_DIR").unwrap())
+//!   .join ("door.rs");
+//! std::fs::write (out, macro_machines::scxml::to_def_machine (&scxml).unwrap())
+//!   .unwrap();
+//!
+//! // src/door.rs
+//! include!(concat!(env!("OUT_DIR"), "/door.rs"));
+//! ```
+//!
+//! Atomic `<state>` and `<final>` elements become states, where the first
+//! `<final>` element is the terminal state. Transitions of compound states
+//! become one event for each atomic descendant state, transitions to compound
+//! states target their initial atomic state, targetless transitions become
+//! internal events, and each event action is a `todo!()` block. The
+//! data model, scripts and conditions are not imported. Since each event of a
+//! machine has a single source state, transitions on the same event from
+//! different states become separate events named `<Event>From<State>`, and
+//! further transitions on the same event from the same state, e.g. with
+//! different conditions, are numbered `<Event>2`, `<Event>3`, and so on. SCXML
+//! IDs and event names are converted to `CamelCase` identifiers, where IDs
+//! that convert to the same identifier are numbered `<State>2`, `<State>3`,
+//! and so on, and states without an ID are named `State1`, `State2`, and so
+//! on.
+
+use std::collections::HashMap;
+use std::fmt::Write;
+
+/// An SCXML document that can not be converted
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ImportError (pub String);
+
+impl std::fmt::Display for ImportError {
+  fn fmt (&self, f : &mut std::fmt::Formatter) -> std::fmt::Result {
+    write!(f, "SCXML import error: {}", self.0)
+  }
+}
+
+impl std::error::Error for ImportError { }
+
+impl From <roxmltree::Error> for ImportError {
+  fn from (err : roxmltree::Error) -> Self {
+    ImportError (err.to_string())
+  }
+}
+
+struct Transition {
+  event  : String,
+  source : String,
+  target : Option <String>
+}
+
+/// Names of the atomic states
+type Names = HashMap <roxmltree::NodeId, String>;
+
+/// Generate a `def_machine!` invocation from an SCXML document
+///
+/// # Errors
+///
+/// Returns an error if the document can not be parsed or uses features that
+/// can not be represented: parallel states, eventless transitions, or
+/// transitions with more than one target.
+pub fn to_def_machine (scxml : &str) -> Result <String, ImportError> {
+  let document = roxmltree::Document::parse (scxml)?;
+  let root     = document.root_element();
+  if root.tag_name().name() != "scxml" {
+    return Err (ImportError ("root element is not <scxml>".to_string()))
+  }
+  let name = identifier (root.attribute ("name").unwrap_or ("Machine"));
+  let names = state_names (&document);
+  let mut states      = Vec::new();
+  let mut terminal    = None;
+  let mut transitions = Vec::new();
+  collect (root, &names, &mut states, &mut terminal, &mut transitions)?;
+  if states.is_empty() {
+    return Err (ImportError ("no states".to_string()))
+  }
+  let initial = initial_state (&names, root)?;
+  // number repeated transitions on the same event from the same state
+  for i in 0..transitions.len() {
+    let (previous, rest) = transitions.split_at_mut (i);
+    let transition = &mut rest[0];
+    let count = previous.iter().filter (|other|
+      other.source == transition.source && other.event == transition.event
+    ).count();
+    if count > 0 {
+      transition.event = format!("{}{}", transition.event, count + 1);
+    }
+  }
+  // disambiguate events with more than one source
+  let mut events : Vec <String> = transitions.iter().map (|transition| {
+    let shared = transitions.iter().any (|other|
+      other.event == transition.event && other.source != transition.source);
+    if shared {
+      format!("{}From{}", transition.event, transition.source)
+    } else {
+      transition.event.clone()
+    }
+  }).collect();
+  // generated names may still collide with other event names
+  for i in 0..events.len() {
+    if events[..i].contains (&events[i]) {
+      let mut n = 2;
+      while events.contains (&format!("{}{n}", events[i])) {
+        n += 1;
+      }
+      events[i] = format!("{}{n}", events[i]);
+    }
+  }
+  let mut s = String::new();
+  writeln!(s, "macro_machines::def_machine!{{\n  {name} () {{\n    STATES [")
+    .unwrap();
+  for state in &states {
+    writeln!(s, "      state {state} ()").unwrap();
+  }
+  s.push_str ("    ]\n    EVENTS [\n");
+  for (transition, event) in transitions.iter().zip (&events) {
+    write!(s, "      event {event} <{}>", transition.source).unwrap();
+    if let Some (target) = transition.target.as_ref() {
+      write!(s, " => <{target}>").unwrap();
+    }
+    s.push_str (" () {} => {\n        todo!()\n      }\n");
+  }
+  writeln!(s, "    ]\n    initial_state: {initial}").unwrap();
+  if let Some (terminal) = terminal {
+    writeln!(s, "    terminal_state: {terminal}").unwrap();
+  }
+  s.push_str ("  }\n}\n");
+  Ok (s)
+}
+
+/// Name each atomic state after its ID, numbering IDs that convert to an
+/// identifier that is already used, and name each atomic state without an ID
+/// `State<N>`, skipping names of states with an ID
+fn state_names (document : &roxmltree::Document) -> Names {
+  let atomic : Vec <_> = document.descendants()
+    .filter (|n| matches!(n.tag_name().name(), "state" | "final"))
+    .filter (|n| !is_compound (*n)).collect();
+  let identifiers : Vec <String> = atomic.iter()
+    .filter_map (|n| n.attribute ("id")).map (identifier).collect();
+  let mut names = Names::new();
+  let mut used  = Vec::new();
+  for node in &atomic {
+    let Some (id) = node.attribute ("id") else { continue };
+    let mut name = identifier (id);
+    if used.contains (&name) {
+      let mut n = 2;
+      while identifiers.contains (&format!("{name}{n}"))
+        || used.contains (&format!("{name}{n}"))
+      {
+        n += 1;
+      }
+      name = format!("{name}{n}");
+    }
+    used.push (name.clone());
+    names.insert (node.id(), name);
+  }
+  let mut anonymous = (1..).map (|n| format!("State{n}"))
+    .filter (|name| !used.contains (name));
+  for node in atomic.iter().filter (|n| n.attribute ("id").is_none()) {
+    names.insert (node.id(), anonymous.next().unwrap());
+  }
+  names
+}
+
+/// Whether the node has child states
+fn is_compound (node : roxmltree::Node) -> bool {
+  node.children()
+    .any (|c| matches!(c.tag_name().name(), "state" | "final" | "parallel"))
+}
+
+/// Collect the atomic states and the transitions of the children of `node`
+fn collect (
+  node        : roxmltree::Node,
+  names       : &Names,
+  states      : &mut Vec <String>,
+  terminal    : &mut Option <String>,
+  transitions : &mut Vec <Transition>
+) -> Result <(), ImportError> {
+  for child in node.children().filter (roxmltree::Node::is_element) {
+    match child.tag_name().name() {
+      "state" => {
+        // the transitions of a compound state apply to each descendant
+        let descendants = if is_compound (child) {
+          let first = states.len();
+          collect (child, names, states, terminal, transitions)?;
+          states[first..].to_vec()
+        } else {
+          let id = names[&child.id()].clone();
+          states.push (id.clone());
+          vec![id]
+        };
+        for transition in child.children()
+          .filter (|c| c.tag_name().name() == "transition")
+        {
+          for source in &descendants {
+            transitions.extend (parse_transition (names, transition, source)?);
+          }
+        }
+      }
+      "final" => {
+        let id = names[&child.id()].clone();
+        if terminal.is_none() {
+          *terminal = Some (id.clone());
+        }
+        states.push (id);
+      }
+      "parallel" => return Err (ImportError (format!(
+        "parallel state {} is not supported",
+        child.attribute ("id").unwrap_or_default()))),
+      _ => {}
+    }
+  }
+  Ok (())
+}
+
+/// One transition per event listed in the `event` attribute
+fn parse_transition (names : &Names, node : roxmltree::Node, source : &str)
+  -> Result <Vec <Transition>, ImportError>
+{
+  let events = node.attribute ("event").unwrap_or_default();
+  if events.trim().is_empty() {
+    return Err (ImportError (format!(
+      "eventless transition from {source} is not supported")))
+  }
+  let target = match node.attribute ("target").map (str::split_whitespace) {
+    Some (mut targets) => {
+      let target = targets.next()
+        .map (|id| initial_state (names, find_state (node.document(), id)?))
+        .transpose()?;
+      if targets.next().is_some() {
+        return Err (ImportError (format!(
+          "transition on {events} has more than one target")))
+      }
+      target
+    }
+    None => None
+  };
+  Ok (events.split_whitespace().map (|event| Transition {
+    event:  identifier (event),
+    source: source.to_string(),
+    target: target.clone()
+  }).collect())
+}
+
+/// The state with the given ID
+fn find_state <'a, 'input> (
+  document : &'a roxmltree::Document <'input>,
+  id       : &str
+) -> Result <roxmltree::Node <'a, 'input>, ImportError> {
+  document.descendants().find (|n| n.attribute ("id") == Some (id))
+    .ok_or_else (|| ImportError (format!("unknown state {id}")))
+}
+
+/// Resolve the initial atomic state of the given state or of the document
+/// root, following the initial states of compound states
+fn initial_state (names : &Names, node : roxmltree::Node)
+  -> Result <String, ImportError>
+{
+  let mut child_states = node.children().filter (|c|
+    matches!(c.tag_name().name(), "state" | "final"));
+  let Some (first) = child_states.next() else {
+    return Ok (names[&node.id()].clone())
+  };
+  match node.attribute ("initial") {
+    Some (initial) => initial_state (names,
+      find_state (node.document(), initial)?),
+    None => initial_state (names, first)
+  }
+}
+
+/// Convert an SCXML ID or event name to a `CamelCase` identifier
+fn identifier (name : &str) -> String {
+  let mut s : String = name.split (|c : char| !c.is_alphanumeric())
+    .filter (|part| !part.is_empty())
+    .map (|part| {
+      let mut chars = part.chars();
+      chars.next().map (|c| c.to_uppercase().chain (chars).collect::<String>())
+        .unwrap_or_default()
+    }).collect();
+  if s.is_empty() || s.starts_with (|c : char| c.is_numeric()) {
+    s.insert (0, 'S');
+  }
+  s
+}
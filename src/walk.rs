@@ -0,0 +1,148 @@
+//! Random event walks for property-based testing.
+//!
+//! Each generated machine has a `random_walk` method that repeatedly handles a
+//! randomly chosen enabled event and checks an invariant after every step.
+//! Machines with default extended state and event parameters also have
+//! `assert_random_walk` which runs a new machine with a seeded `SplitMix64`
+//! generator and panics with the seed and event trace on failure, otherwise
+//! returning the machine in the state the walk left it in:
+//!
+//! ```text
+//! #[test]
+//! fn door_random_walk() {
+//!   for seed in 0..100 {
+//!     Door::assert_random_walk (seed, 1000, |door| {
+//!       if door.extended_state().open_count < 1000 {
+//!         Ok (())
+//!       } else {
+//!         Err ("open count too large".to_string())
+//!       }
+//!     });
+//!   }
+//! }
+//! ```
+
+use crate::HandleEventException;
+
+/// Source of random numbers for choosing events
+pub trait Rng {
+  fn next_u64 (&mut self) -> u64;
+  /// The seed of the generator, if known, to be reported on failure
+  fn seed (&self) -> Option <u64> {
+    None
+  }
+  /// Random number less than `n`; `n` must be non-zero
+  #[expect(clippy::cast_possible_truncation)]
+  fn below (&mut self, n : usize) -> usize {
+    (self.next_u64() % n as u64) as usize
+  }
+}
+
+/// Closures returning random numbers may be used as generators
+impl <F : FnMut() -> u64> Rng for F {
+  fn next_u64 (&mut self) -> u64 {
+    self()
+  }
+}
+
+/// Small seedable pseudo-random number generator; not suitable for
+/// cryptographic use
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SplitMix64 {
+  seed  : u64,
+  state : u64
+}
+
+impl SplitMix64 {
+  pub const fn new (seed : u64) -> Self {
+    SplitMix64 { seed, state: seed }
+  }
+}
+
+impl Rng for SplitMix64 {
+  fn next_u64 (&mut self) -> u64 {
+    self.state = self.state.wrapping_add (0x9e37_79b9_7f4a_7c15);
+    let mut z = self.state;
+    z = (z ^ (z >> 30)).wrapping_mul (0xbf58_476d_1ce4_e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul (0x94d0_49bb_1331_11eb);
+    z ^ (z >> 31)
+  }
+  fn seed (&self) -> Option <u64> {
+    Some (self.seed)
+  }
+}
+
+/// A failed random walk
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Failure <E> {
+  /// Seed of the generator, if known
+  pub seed    : Option <u64>,
+  /// Events handled up to and including the failing one
+  pub trace   : Vec <E>,
+  /// The violated invariant, rejection or panic message
+  pub message : String
+}
+
+impl <E : std::fmt::Debug> std::fmt::Display for Failure <E> {
+  fn fmt (&self, f : &mut std::fmt::Formatter) -> std::fmt::Result {
+    write!(f, "random walk failed after {} events", self.trace.len())?;
+    if let Some (seed) = self.seed {
+      write!(f, " (seed {seed})")?;
+    }
+    write!(f, ": {}\nevent trace: {:?}", self.message, self.trace)
+  }
+}
+
+/// Handle up to `steps` events chosen at random among the `enabled` events of
+/// the machine with `handle`, checking the `invariant` after each step.
+///
+/// The walk ends early if no events are enabled. Panics while handling an
+/// event are caught and reported as failures.
+///
+/// # Errors
+///
+/// Returns the event trace if an event is rejected, handling an event panics
+/// or the invariant does not hold.
+pub fn run <M, E, R, F, H, I> (
+  machine       : &mut M,
+  rng           : &mut R,
+  steps         : usize,
+  enabled       : F,
+  mut handle    : H,
+  mut invariant : I
+) -> Result <(), Failure <E>> where
+  E : Clone,
+  R : Rng,
+  F : Fn (&M) -> Vec <E>,
+  H : FnMut (&mut M, &E, &mut R) -> Result <(), HandleEventException>,
+  I : FnMut (&M) -> Result <(), String>
+{
+  let mut trace = Vec::new();
+  for _ in 0..steps {
+    let events = enabled (machine);
+    if events.is_empty() {
+      break
+    }
+    let event = events[rng.below (events.len())].clone();
+    trace.push (event.clone());
+    let result = std::panic::catch_unwind (std::panic::AssertUnwindSafe (
+      || handle (machine, &event, rng)));
+    let message = match result {
+      Ok (Ok (())) => match invariant (machine) {
+        Ok (()) => continue,
+        Err (message) => format!("invariant violated: {message}")
+      }
+      Ok (Err (exception)) => format!("event rejected: {exception:?}"),
+      Err (payload) => format!("panicked: {}", panic_message (&*payload))
+    };
+    return Err (Failure { seed: rng.seed(), trace, message })
+  }
+  Ok (())
+}
+
+/// Message of a caught panic
+fn panic_message (payload : &(dyn std::any::Any + Send)) -> &str {
+  payload.downcast_ref::<&str>().copied()
+    .or_else (|| payload.downcast_ref::<String>().map (String::as_str))
+    .unwrap_or ("<non-string panic payload>")
+}
@@ -0,0 +1,18 @@
+use macro_machines::def_machine;
+
+def_machine!{
+  Door () {
+    STATES [
+      state Closed ()
+      state Opened ()
+    ]
+    EVENTS [
+      event Open <Closed> => <Opened> ()
+      event Close <Opened> => <Closed> ()
+      event Open <Opened> => <Opened> ()
+    ]
+    initial_state: Closed
+  }
+}
+
+fn main() {}
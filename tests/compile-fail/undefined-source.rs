@@ -0,0 +1,16 @@
+use macro_machines::def_machine;
+
+def_machine!{
+  Door () {
+    STATES [
+      state Closed ()
+      state Opened ()
+    ]
+    EVENTS [
+      event Open <Clsoed> => <Opened> ()
+    ]
+    initial_state: Closed
+  }
+}
+
+fn main() {}
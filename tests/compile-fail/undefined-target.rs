@@ -0,0 +1,16 @@
+use macro_machines::def_machine;
+
+def_machine!{
+  Door () {
+    STATES [
+      state Closed ()
+      state Opened ()
+    ]
+    EVENTS [
+      event Open <Closed> => <Opend> ()
+    ]
+    initial_state: Closed
+  }
+}
+
+fn main() {}
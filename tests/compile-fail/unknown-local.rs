@@ -0,0 +1,17 @@
+use macro_machines::def_machine;
+
+def_machine!{
+  Door () {
+    STATES [
+      state Closed (knock_count : u64)
+      state Opened ()
+    ]
+    EVENTS [
+      event Knock <Closed> () { knock_cont } => { *knock_cont += 1; }
+      event Open <Closed> => <Opened> ()
+    ]
+    initial_state: Closed
+  }
+}
+
+fn main() {}